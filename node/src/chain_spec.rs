@@ -1,12 +1,16 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	opaque::SessionKeys, AccountId, AuraConfig, Balance, BalancesConfig, BlockNumber,
+	BlockRewardConfig, CouncilConfig, GenesisConfig, GrandpaConfig, SS58Prefix, SessionConfig,
+	Signature, SudoConfig, SystemConfig, ValidatorSetConfig, VestingConfig, TOKEN_DECIMALS,
+	TOKEN_SYMBOL, UNIT, WASM_BINARY,
 };
-use sc_service::ChainType;
+use pallet_evm::{AddressMapping, HashedAddressMapping};
+use sc_service::{ChainType, Properties};
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{sr25519, Pair, Public, H160};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
-use sp_runtime::traits::{IdentifyAccount, Verify};
+use sp_runtime::traits::{BlakeTwo256, IdentifyAccount, Verify};
+use std::str::FromStr;
 
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
@@ -31,9 +35,40 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
-	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
+/// The account an EVM address is mapped to, which holds the address's balance.
+pub fn evm_account(address: H160) -> AccountId {
+	HashedAddressMapping::<BlakeTwo256>::into_account_id(address)
+}
+
+/// The first of the well-known development accounts of Hardhat and similar Ethereum tooling.
+///
+/// Its private key is public, so it must never hold value.
+pub fn dev_evm_account() -> AccountId {
+	evm_account(
+		H160::from_str("f39fd6e51aad88f6f4ce6ab8827279cfffb92266").expect("valid address; qed"),
+	)
+}
+
+/// Generate a validator account together with its Aura and GRANDPA session keys.
+pub fn authority_keys_from_seed(s: &str) -> (AccountId, AuraId, GrandpaId) {
+	(
+		get_account_id_from_seed::<sr25519::Public>(s),
+		get_from_seed::<AuraId>(s),
+		get_from_seed::<GrandpaId>(s),
+	)
+}
+
+/// The token and address properties of the chain, as defined by the runtime.
+pub fn properties() -> Properties {
+	let mut properties = Properties::new();
+	properties.insert("tokenSymbol".into(), TOKEN_SYMBOL.into());
+	properties.insert("tokenDecimals".into(), TOKEN_DECIMALS.into());
+	properties.insert("ss58Format".into(), SS58Prefix::get().into());
+	properties
+}
+
+fn session_keys(aura: AuraId, grandpa: GrandpaId) -> SessionKeys {
+	SessionKeys { aura, grandpa }
 }
 
 pub fn development_config() -> Result<ChainSpec, String> {
@@ -52,12 +87,24 @@ pub fn development_config() -> Result<ChainSpec, String> {
 				vec![authority_keys_from_seed("Alice")],
 				// Sudo account
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				// Pre-funded accounts
+				// Initial council members
 				vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
 					get_account_id_from_seed::<sr25519::Public>("Bob"),
+				],
+				// Pre-funded accounts. All well-known development accounts are funded, so that
+				// contract tooling can deploy and call contracts from any of them.
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+					get_account_id_from_seed::<sr25519::Public>("Charlie"),
+					get_account_id_from_seed::<sr25519::Public>("Dave"),
+					get_account_id_from_seed::<sr25519::Public>("Eve"),
+					get_account_id_from_seed::<sr25519::Public>("Ferdie"),
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
+					// Ethereum tooling signs with this one by default.
+					dev_evm_account(),
 				],
 				true,
 			)
@@ -69,7 +116,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		// Protocol ID
 		None,
 		// Properties
-		None,
+		Some(properties()),
 		// Extensions
 		None,
 	))
@@ -91,6 +138,12 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
 				// Sudo account
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				// Initial council members
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+					get_account_id_from_seed::<sr25519::Public>("Charlie"),
+				],
 				// Pre-funded accounts
 				vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
@@ -116,7 +169,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		// Protocol ID
 		None,
 		// Properties
-		None,
+		Some(properties()),
 		// Extensions
 		None,
 	))
@@ -125,30 +178,73 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
 	wasm_binary: &[u8],
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
+	initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
 	root_key: AccountId,
+	council: Vec<AccountId>,
 	endowed_accounts: Vec<AccountId>,
 	_enable_println: bool,
+) -> GenesisConfig {
+	// Configure endowed accounts with an initial balance of a million units.
+	let endowments = endowed_accounts.into_iter().map(|k| (k, 1_000_000 * UNIT)).collect();
+
+	// Authors are paid one unit per block, as they author it.
+	let block_reward = (UNIT, None);
+
+	genesis(wasm_binary, initial_authorities, root_key, council, endowments, vec![], block_reward)
+}
+
+/// A vesting schedule at genesis: the account, the block it starts unlocking at, the number of
+/// blocks it unlocks over and the part of the account's endowment that is liquid from the start.
+pub type VestingSchedule = (AccountId, BlockNumber, BlockNumber, Balance);
+
+/// The block reward at genesis: the amount minted per block and, if rewards are paid per era
+/// rather than with every block, the length of an era.
+pub type BlockReward = (Balance, Option<BlockNumber>);
+
+/// Build the genesis storage from the initial authorities, sudo key, council, balances, the
+/// vesting schedules locking part of them and the block reward.
+pub fn genesis(
+	wasm_binary: &[u8],
+	initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
+	root_key: AccountId,
+	council: Vec<AccountId>,
+	endowments: Vec<(AccountId, Balance)>,
+	vesting: Vec<VestingSchedule>,
+	block_reward: BlockReward,
 ) -> GenesisConfig {
 	GenesisConfig {
 		system: SystemConfig {
 			// Add Wasm runtime to storage.
 			code: wasm_binary.to_vec(),
 		},
-		balances: BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
+		balances: BalancesConfig { balances: endowments },
+		vesting: VestingConfig { vesting },
+		block_reward: BlockRewardConfig {
+			reward_per_block: block_reward.0,
+			era_length: block_reward.1,
 		},
-		aura: AuraConfig {
-			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
+		validator_set: ValidatorSetConfig {
+			validators: initial_authorities.iter().map(|x| x.0.clone()).collect(),
 		},
-		grandpa: GrandpaConfig {
-			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
+		session: SessionConfig {
+			keys: initial_authorities
+				.iter()
+				.map(|x| (x.0.clone(), x.0.clone(), session_keys(x.1.clone(), x.2.clone())))
+				.collect(),
 		},
+		// Aura and GRANDPA authorities are set up by the session pallet from the keys above.
+		aura: AuraConfig { authorities: vec![] },
+		grandpa: GrandpaConfig { authorities: vec![] },
 		sudo: SudoConfig {
 			// Assign network admin rights.
 			key: root_key,
 		},
 		transaction_payment: Default::default(),
+		council: CouncilConfig { phantom: Default::default(), members: council },
+		democracy: Default::default(),
+		treasury: Default::default(),
+		evm: Default::default(),
+		ethereum: Default::default(),
+		base_fee: Default::default(),
 	}
 }
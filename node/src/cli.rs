@@ -1,4 +1,5 @@
 use sc_cli::RunCmd;
+use std::str::FromStr;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -8,6 +9,48 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// Seal blocks on demand instead of running Aura and GRANDPA, for development and testing.
+	///
+	/// `instant` seals a block for every transaction entering the pool, `manual` only seals when
+	/// asked through the `engine_createBlock` RPC and `interval=<ms>` seals a block every given
+	/// number of milliseconds.
+	#[structopt(long)]
+	pub sealing: Option<Sealing>,
+}
+
+/// How blocks are sealed when Aura and GRANDPA are swapped for manual seal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sealing {
+	/// Seal a block as soon as a transaction enters the pool.
+	Instant,
+	/// Seal blocks only when requested through the RPC.
+	Manual,
+	/// Seal a block every given number of milliseconds.
+	Interval(u64),
+}
+
+impl FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"instant" => Ok(Self::Instant),
+			"manual" => Ok(Self::Manual),
+			s => match s.strip_prefix("interval=") {
+				Some(millis) => millis
+					.parse()
+					.ok()
+					.filter(|millis| *millis > 0)
+					.map(Self::Interval)
+					.ok_or_else(|| format!("Invalid sealing interval `{}`", millis)),
+				None => Err(format!(
+					"Invalid sealing `{}`, expected `instant`, `manual` or `interval=<ms>`",
+					s
+				)),
+			},
+		}
+	}
 }
 
 #[derive(Debug, StructOpt)]
@@ -38,4 +81,13 @@ pub enum Subcommand {
 	/// The custom benchmark subcommand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// Try some command against runtime state, e.g. run the runtime upgrade hooks or execute a
+	/// block against a state snapshot.
+	#[cfg(feature = "try-runtime")]
+	TryRuntime(try_runtime_cli::TryRuntimeCmd),
+
+	/// Try some command against runtime state. Note: `try-runtime` feature must be enabled.
+	#[cfg(not(feature = "try-runtime"))]
+	TryRuntime,
 }
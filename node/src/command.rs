@@ -1,11 +1,12 @@
 use crate::{
 	chain_spec,
-	cli::{Cli, Subcommand},
+	cli::{Cli, Sealing, Subcommand},
+	genesis::GenesisFile,
 	service,
 };
 use node_template_runtime::Block;
 use sc_cli::{ChainSpec, RuntimeVersion, SubstrateCli};
-use sc_service::PartialComponents;
+use sc_service::{ChainType, PartialComponents};
 
 impl SubstrateCli for Cli {
 	fn impl_name() -> String {
@@ -36,6 +37,8 @@ impl SubstrateCli for Cli {
 		Ok(match id {
 			"dev" => Box::new(chain_spec::development_config()?),
 			"" | "local" => Box::new(chain_spec::local_testnet_config()?),
+			path if GenesisFile::is_genesis_file(path) =>
+				Box::new(GenesisFile::from_file(path)?.into_chain_spec()?),
 			path =>
 				Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
 		})
@@ -46,6 +49,17 @@ impl SubstrateCli for Cli {
 	}
 }
 
+/// Manual seal hands authoring and finality to whoever can reach the `engine_*` RPCs, and the
+/// blocks it seals are rejected by every node running Aura, so it is kept off shared chains.
+fn ensure_sealing_allowed(sealing: Option<Sealing>, chain_type: &ChainType) -> sc_cli::Result<()> {
+	match (sealing, chain_type) {
+		(Some(_), ChainType::Live | ChainType::Custom(_)) => Err(sc_cli::Error::Input(
+			"`--sealing` is only allowed on development and local chains".into(),
+		)),
+		_ => Ok(()),
+	}
+}
+
 /// Parse and run command line arguments
 pub fn run() -> sc_cli::Result<()> {
 	let cli = Cli::from_args();
@@ -59,43 +73,79 @@ pub fn run() -> sc_cli::Result<()> {
 		Some(Subcommand::CheckBlock(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
+				// Blocks from outside are checked with the Aura import queue, whatever `--sealing`
+				// says, so that their seals are verified.
 				let PartialComponents { client, task_manager, import_queue, .. } =
-					service::new_partial(&config)?;
+					service::new_partial(&config, None)?;
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
 		Some(Subcommand::ExportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
-				let PartialComponents { client, task_manager, .. } = service::new_partial(&config)?;
+				let PartialComponents { client, task_manager, .. } =
+					service::new_partial(&config, None)?;
 				Ok((cmd.run(client, config.database), task_manager))
 			})
 		},
 		Some(Subcommand::ExportState(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
-				let PartialComponents { client, task_manager, .. } = service::new_partial(&config)?;
+				let PartialComponents { client, task_manager, .. } =
+					service::new_partial(&config, None)?;
 				Ok((cmd.run(client, config.chain_spec), task_manager))
 			})
 		},
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
+				if !config.offchain_worker.indexing_enabled {
+					log::warn!(
+						"Offchain indexing is disabled, so `template_history` won't return the \
+						 history of the imported blocks. Sync them with \
+						 `--enable-offchain-indexing true` instead to index them."
+					);
+				}
+				// Verify Aura seals, as `check-block` does.
 				let PartialComponents { client, task_manager, import_queue, .. } =
-					service::new_partial(&config)?;
+					service::new_partial(&config, None)?;
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
 		Some(Subcommand::PurgeChain(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
-			runner.sync_run(|config| cmd.run(config.database))
+			runner.sync_run(|config| {
+				let frontier_path = service::frontier_database_dir(&config);
+				let chain_path = config.database.path().map(|path| path.to_path_buf());
+				cmd.run(config.database)?;
+				// The Ethereum mappings point at the blocks just removed. Left in place if the
+				// purge was declined.
+				if chain_path.map_or(false, |path| !path.exists()) && frontier_path.exists() {
+					std::fs::remove_dir_all(&frontier_path)?;
+				}
+				Ok(())
+			})
 		},
 		Some(Subcommand::Revert(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
-				let PartialComponents { client, task_manager, backend, .. } =
-					service::new_partial(&config)?;
-				Ok((cmd.run(client, backend), task_manager))
+				let PartialComponents { client, task_manager, backend, other, .. } =
+					service::new_partial(&config, None)?;
+				// The Ethereum mappings can't be reverted. Remove them once the blocks are, the
+				// mapping sync worker rebuilds them from the remaining chain on the next start.
+				drop(other);
+				let frontier_database_dir = service::frontier_database_dir(&config);
+				let revert = cmd.run(client, backend);
+				Ok((
+					async move {
+						revert.await?;
+						if frontier_database_dir.exists() {
+							std::fs::remove_dir_all(frontier_database_dir)?;
+						}
+						Ok(())
+					},
+					task_manager,
+				))
 			})
 		},
 		Some(Subcommand::Benchmark(cmd)) =>
@@ -108,11 +158,51 @@ pub fn run() -> sc_cli::Result<()> {
 				     `--features runtime-benchmarks`."
 					.into())
 			},
+		#[cfg(feature = "try-runtime")]
+		Some(Subcommand::TryRuntime(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				// The command doesn't start a service, it only needs a task manager to run on.
+				let registry = config.prometheus_config.as_ref().map(|cfg| &cfg.registry);
+				let task_manager =
+					sc_service::TaskManager::new(config.tokio_handle.clone(), registry)
+						.map_err(|e| sc_cli::Error::Service(sc_service::Error::Prometheus(e)))?;
+				Ok((cmd.run::<Block, service::ExecutorDispatch>(config), task_manager))
+			})
+		},
+		#[cfg(not(feature = "try-runtime"))]
+		Some(Subcommand::TryRuntime) => Err("TryRuntime wasn't enabled when building the node. \
+				You can enable it with `--features try-runtime`."
+			.into()),
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
+			ensure_sealing_allowed(cli.sealing, &runner.config().chain_spec.chain_type())?;
 			runner.run_node_until_exit(|config| async move {
-				service::new_full(config).map_err(sc_cli::Error::Service)
+				if !config.offchain_worker.indexing_enabled {
+					log::warn!(
+						"Offchain indexing is disabled, so `template_history` won't return the \
+						 history of the blocks imported now. Start the node with \
+						 `--enable-offchain-indexing true` to index them."
+					);
+				}
+				service::new_full(config, cli.sealing).map_err(sc_cli::Error::Service)
 			})
 		},
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn sealing_is_rejected_on_shared_chains() {
+		for chain_type in [ChainType::Live, ChainType::Custom("staging".into())] {
+			assert!(ensure_sealing_allowed(Some(Sealing::Instant), &chain_type).is_err());
+			assert!(ensure_sealing_allowed(None, &chain_type).is_ok());
+		}
+		for chain_type in [ChainType::Development, ChainType::Local] {
+			assert!(ensure_sealing_allowed(Some(Sealing::Manual), &chain_type).is_ok());
+		}
+	}
+}
@@ -0,0 +1,568 @@
+//! Chain specifications described by a TOML or YAML file instead of Rust code.
+//!
+//! A genesis file names the chain and lists its authorities, sudo key, council, endowments,
+//! vesting schedules and block reward, so that a new network can be set up without recompiling
+//! the node:
+//!
+//! ```toml
+//! name = "My Network"
+//! id = "my_network"
+//! chain_type = "Live"
+//! sudo = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+//! council = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+//! bootnodes = ["/dns/boot.example.com/tcp/30333/p2p/12D3KooW..."]
+//!
+//! [properties]
+//! tokenSymbol = "UNIT"
+//!
+//! [[authorities]]
+//! account = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+//! aura = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+//! grandpa = "0x88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee"
+//!
+//! [[endowments]]
+//! account = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+//! balance = "1000000000000000000000000"
+//!
+//! [[vesting]]
+//! account = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+//! begin = 100
+//! length = 1000000
+//! liquid = "100000000000000000000000"
+//!
+//! [block_reward]
+//! per_block = "1000000000000000000"
+//! era_length = 600
+//! ```
+//!
+//! Without a `block_reward` section, no block rewards are minted.
+//!
+//! Accounts and keys are given in SS58 or as 32 bytes of hex. Balances can be given as a string,
+//! since they may not fit the integer types of TOML and YAML.
+
+use crate::chain_spec::{self, BlockReward, ChainSpec, VestingSchedule};
+use node_template_runtime::{AccountId, Balance, BlockNumber, ExistentialDeposit, WASM_BINARY};
+use sc_service::{config::MultiaddrWithPeerId, ChainType, Properties};
+use serde::Deserialize;
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_core::{crypto::Ss58Codec, ed25519, sr25519};
+use sp_finality_grandpa::AuthorityId as GrandpaId;
+use std::{collections::BTreeSet, fmt::Display, path::Path};
+
+/// The contents of a genesis file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisFile {
+	/// Human readable name of the chain.
+	pub name: String,
+	/// Identifier of the chain, also used to name its directory in the base path.
+	pub id: String,
+	/// The kind of chain, `Development`, `Local` or `Live`.
+	#[serde(default = "default_chain_type")]
+	pub chain_type: ChainType,
+	/// Network protocol id, to keep the chain apart from other chains on the same network.
+	#[serde(default)]
+	pub protocol_id: Option<String>,
+	/// Addresses of the nodes a new node connects to first.
+	#[serde(default)]
+	pub bootnodes: Vec<String>,
+	/// Token and address format properties reported to user interfaces. `tokenSymbol`,
+	/// `tokenDecimals` and `ss58Format` are taken from the runtime and may be left out; if given,
+	/// they must match the runtime's values.
+	#[serde(default)]
+	pub properties: Option<Properties>,
+	/// The validators of the first session.
+	pub authorities: Vec<Authority>,
+	/// The sudo key.
+	pub sudo: String,
+	/// The members of the initial council.
+	#[serde(default)]
+	pub council: Vec<String>,
+	/// The accounts funded at genesis.
+	#[serde(default)]
+	pub endowments: Vec<Endowment>,
+	/// The endowments that unlock gradually.
+	#[serde(default)]
+	pub vesting: Vec<Vesting>,
+	/// The reward minted for block authors. None is minted if left out.
+	#[serde(default)]
+	pub block_reward: Option<Reward>,
+}
+
+/// A validator of the first session, with its session keys.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Authority {
+	/// The validator's account.
+	pub account: String,
+	/// The validator's Aura key (sr25519).
+	pub aura: String,
+	/// The validator's GRANDPA key (ed25519).
+	pub grandpa: String,
+}
+
+/// An account funded at genesis.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Endowment {
+	/// The funded account.
+	pub account: String,
+	/// The account's free balance.
+	pub balance: BalanceValue,
+}
+
+/// An endowment that is locked at genesis and unlocks linearly.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Vesting {
+	/// The endowed account.
+	pub account: String,
+	/// The block the endowment starts unlocking at.
+	#[serde(default)]
+	pub begin: BlockNumber,
+	/// The number of blocks the endowment unlocks over.
+	pub length: BlockNumber,
+	/// The part of the endowment that is not locked at all.
+	#[serde(default)]
+	pub liquid: Option<BalanceValue>,
+}
+
+/// The reward minted for the author of each block.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Reward {
+	/// The amount minted per block.
+	pub per_block: BalanceValue,
+	/// The number of blocks after which authors are paid for the blocks they authored, if they
+	/// are not paid with every block.
+	#[serde(default)]
+	pub era_length: Option<BlockNumber>,
+}
+
+/// A balance given either as an integer or, for amounts too large for the file format, as a
+/// string of decimal digits.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum BalanceValue {
+	/// A balance given as an integer.
+	Number(u64),
+	/// A balance given as a string.
+	Text(String),
+}
+
+fn default_chain_type() -> ChainType {
+	ChainType::Live
+}
+
+impl GenesisFile {
+	/// Whether `path` names a file this module can read, judging by its extension.
+	pub fn is_genesis_file(path: &str) -> bool {
+		matches!(
+			Path::new(path).extension().and_then(|e| e.to_str()),
+			Some("toml" | "yaml" | "yml")
+		)
+	}
+
+	/// Read a genesis file, picking the format from the file's extension.
+	pub fn from_file(path: &str) -> Result<Self, String> {
+		let contents = std::fs::read_to_string(path)
+			.map_err(|e| format!("Error reading genesis file `{}`: {}", path, e))?;
+
+		match Path::new(path).extension().and_then(|e| e.to_str()) {
+			Some("toml") => toml::from_str(&contents).map_err(|e| e.to_string()),
+			Some("yaml" | "yml") => serde_yaml::from_str(&contents).map_err(|e| e.to_string()),
+			_ => Err("Genesis files must end in `.toml`, `.yaml` or `.yml`".into()),
+		}
+		.map_err(|e| format!("Error parsing genesis file `{}`: {}", path, e))
+	}
+
+	/// Check the description and turn it into the inputs of [`chain_spec::genesis`].
+	pub fn validate(&self) -> Result<Genesis, String> {
+		if self.name.trim().is_empty() || self.id.trim().is_empty() {
+			return Err("`name` and `id` must not be empty".into())
+		}
+		if self.authorities.is_empty() {
+			return Err("At least one authority is required".into())
+		}
+
+		let authorities = self
+			.authorities
+			.iter()
+			.map(|a| {
+				Ok((
+					parse_key("authority account", &a.account, AccountId::from)?,
+					parse_key("aura key", &a.aura, sr25519::Public::from_raw)?.into(),
+					parse_key("grandpa key", &a.grandpa, ed25519::Public::from_raw)?.into(),
+				))
+			})
+			.collect::<Result<Vec<(AccountId, AuraId, GrandpaId)>, String>>()?;
+		ensure_unique("authority", authorities.iter().map(|a| &a.0))?;
+		// The session pallet panics on duplicate keys while building the genesis storage.
+		ensure_unique("aura key", authorities.iter().map(|a| &a.1))?;
+		ensure_unique("grandpa key", authorities.iter().map(|a| &a.2))?;
+
+		let sudo = parse_key("sudo key", &self.sudo, AccountId::from)?;
+
+		let council = self
+			.council
+			.iter()
+			.map(|c| parse_key("council member", c, AccountId::from))
+			.collect::<Result<Vec<_>, _>>()?;
+		ensure_unique("council member", council.iter())?;
+
+		let endowments = self
+			.endowments
+			.iter()
+			.map(|e| {
+				let account = parse_key("endowed account", &e.account, AccountId::from)?;
+				let balance = e.balance.to_balance()?;
+				if balance < ExistentialDeposit::get() {
+					return Err(format!(
+						"Endowment of {} is below the existential deposit of {}",
+						e.account,
+						ExistentialDeposit::get()
+					))
+				}
+				Ok((account, balance))
+			})
+			.collect::<Result<Vec<_>, String>>()?;
+		ensure_unique("endowed account", endowments.iter().map(|e| &e.0))?;
+
+		let vesting =
+			self.vesting
+				.iter()
+				.map(|v| {
+					let account = parse_key("vesting account", &v.account, AccountId::from)?;
+					let endowment =
+						endowments.iter().find(|e| e.0 == account).map(|e| e.1).ok_or_else(
+							|| format!("Vesting account {} is not endowed", v.account),
+						)?;
+					let liquid = v.liquid.as_ref().map(BalanceValue::to_balance).transpose()?;
+					let locked = endowment.saturating_sub(liquid.unwrap_or_default());
+					// The runtime refuses schedules that unlock nothing per block.
+					if v.length == 0 || locked < v.length.into() {
+						return Err(format!(
+							"Vesting of {} must lock at least one unit per block of its length",
+							v.account
+						))
+					}
+					Ok((account, v.begin, v.length, liquid.unwrap_or_default()))
+				})
+				.collect::<Result<Vec<VestingSchedule>, String>>()?;
+		ensure_unique("vesting account", vesting.iter().map(|v| &v.0))?;
+
+		let block_reward = match &self.block_reward {
+			Some(reward) => {
+				if reward.era_length == Some(0) {
+					return Err("Block reward eras must be at least one block long".into())
+				}
+				(reward.per_block.to_balance()?, reward.era_length)
+			},
+			None => (0, None),
+		};
+
+		let boot_nodes = self
+			.bootnodes
+			.iter()
+			.map(|b| b.parse().map_err(|e| format!("Invalid bootnode `{}`: {}", b, e)))
+			.collect::<Result<Vec<MultiaddrWithPeerId>, String>>()?;
+
+		let mut properties = chain_spec::properties();
+		for (key, value) in self.properties.iter().flatten() {
+			match properties.get(key) {
+				Some(expected) if expected != value =>
+					return Err(format!(
+						"Property `{}` is {} in the genesis file, but {} in the runtime",
+						key, value, expected
+					)),
+				_ => {
+					properties.insert(key.clone(), value.clone());
+				},
+			}
+		}
+
+		Ok(Genesis {
+			authorities,
+			sudo,
+			council,
+			endowments,
+			vesting,
+			block_reward,
+			boot_nodes,
+			properties,
+		})
+	}
+
+	/// Validate the description and build the chain specification it describes.
+	pub fn into_chain_spec(self) -> Result<ChainSpec, String> {
+		let wasm_binary = WASM_BINARY.ok_or_else(|| "Wasm binary not available".to_string())?;
+		let Genesis {
+			authorities,
+			sudo,
+			council,
+			endowments,
+			vesting,
+			block_reward,
+			boot_nodes,
+			properties,
+		} = self.validate()?;
+
+		Ok(ChainSpec::from_genesis(
+			&self.name,
+			&self.id,
+			self.chain_type,
+			move || {
+				chain_spec::genesis(
+					wasm_binary,
+					authorities.clone(),
+					sudo.clone(),
+					council.clone(),
+					endowments.clone(),
+					vesting.clone(),
+					block_reward,
+				)
+			},
+			boot_nodes,
+			None,
+			self.protocol_id.as_deref(),
+			Some(properties),
+			None,
+		))
+	}
+}
+
+/// The checked contents of a [`GenesisFile`].
+pub struct Genesis {
+	/// The validators of the first session with their Aura and GRANDPA keys.
+	pub authorities: Vec<(AccountId, AuraId, GrandpaId)>,
+	/// The sudo key.
+	pub sudo: AccountId,
+	/// The members of the initial council.
+	pub council: Vec<AccountId>,
+	/// The accounts funded at genesis.
+	pub endowments: Vec<(AccountId, Balance)>,
+	/// The vesting schedules of endowed accounts.
+	pub vesting: Vec<VestingSchedule>,
+	/// The reward minted per block and the era length, if rewards are paid per era.
+	pub block_reward: BlockReward,
+	/// Addresses of the nodes a new node connects to first.
+	pub boot_nodes: Vec<MultiaddrWithPeerId>,
+	/// The runtime's token and address properties, together with any others from the file.
+	pub properties: Properties,
+}
+
+impl BalanceValue {
+	fn to_balance(&self) -> Result<Balance, String> {
+		match self {
+			Self::Number(n) => Ok((*n).into()),
+			Self::Text(s) => s.parse().map_err(|e| format!("Invalid balance `{}`: {}", s, e)),
+		}
+	}
+}
+
+/// Parse a 32 byte key given in SS58 or hex.
+fn parse_key<T: Ss58Codec>(
+	what: &str,
+	value: &str,
+	from_raw: impl FnOnce([u8; 32]) -> T,
+) -> Result<T, String> {
+	if value.starts_with("0x") {
+		let bytes = sp_core::bytes::from_hex(value)
+			.map_err(|e| format!("Invalid {} `{}`: {:?}", what, value, e))?;
+		let raw = <[u8; 32]>::try_from(bytes)
+			.map_err(|_| format!("Invalid {} `{}`: expected 32 bytes", what, value))?;
+		Ok(from_raw(raw))
+	} else {
+		T::from_ss58check(value).map_err(|e| format!("Invalid {} `{}`: {:?}", what, value, e))
+	}
+}
+
+fn ensure_unique<'a, T: Ord + Display + 'a>(
+	what: &str,
+	items: impl Iterator<Item = &'a T>,
+) -> Result<(), String> {
+	let mut seen = BTreeSet::new();
+	for item in items {
+		if !seen.insert(item) {
+			return Err(format!("Duplicate {} {}", what, item))
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chain_spec::{authority_keys_from_seed, get_account_id_from_seed};
+
+	const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+	const ALICE_GRANDPA: &str = "5FA9nQDVg267DEd8m1ZypXLBnvN7SFxYwV7ndqSYGiN9TTpu";
+	const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+
+	fn alice_only() -> String {
+		format!(
+			r#"
+name = "Test"
+id = "test"
+chain_type = "Local"
+sudo = "{alice}"
+council = ["{alice}", "{bob}"]
+
+[properties]
+tokenSymbol = "UNIT"
+
+[[authorities]]
+account = "{alice}"
+aura = "{alice}"
+grandpa = "{grandpa}"
+
+[[endowments]]
+account = "{alice}"
+balance = "1000000000000000000000"
+
+[[endowments]]
+account = "{bob}"
+balance = 1000
+
+[[vesting]]
+account = "{alice}"
+begin = 10
+length = 100
+liquid = "1000"
+
+[block_reward]
+per_block = 500
+era_length = 10
+"#,
+			alice = ALICE,
+			bob = BOB,
+			grandpa = ALICE_GRANDPA,
+		)
+	}
+
+	#[test]
+	fn parses_toml() {
+		let file: GenesisFile = toml::from_str(&alice_only()).unwrap();
+		let genesis = file.validate().unwrap();
+
+		assert_eq!(file.chain_type, ChainType::Local);
+		assert_eq!(genesis.properties, chain_spec::properties());
+		assert_eq!(genesis.authorities, vec![authority_keys_from_seed("Alice")]);
+		assert_eq!(genesis.sudo, get_account_id_from_seed::<sr25519::Public>("Alice"));
+		assert_eq!(genesis.council.len(), 2);
+		assert_eq!(
+			genesis.endowments,
+			vec![
+				(
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					1_000_000_000_000_000_000_000
+				),
+				(get_account_id_from_seed::<sr25519::Public>("Bob"), 1000),
+			]
+		);
+		assert_eq!(
+			genesis.vesting,
+			vec![(get_account_id_from_seed::<sr25519::Public>("Alice"), 10, 100, 1000)]
+		);
+		assert_eq!(genesis.block_reward, (500, Some(10)));
+	}
+
+	fn hex(key: &impl AsRef<[u8]>) -> String {
+		sp_core::bytes::to_hex(key.as_ref(), false)
+	}
+
+	#[test]
+	fn parses_yaml_with_hex_keys() {
+		let (account, aura, grandpa) = authority_keys_from_seed("Alice");
+		let yaml = format!(
+			r#"
+name: Test
+id: test
+sudo: "{alice}"
+authorities:
+  - account: "{account}"
+    aura: "{aura}"
+    grandpa: "{grandpa}"
+"#,
+			alice = ALICE,
+			account = hex(&account),
+			aura = hex(&aura),
+			grandpa = hex(&grandpa),
+		);
+
+		let file: GenesisFile = serde_yaml::from_str(&yaml).unwrap();
+		let genesis = file.validate().unwrap();
+
+		assert_eq!(file.chain_type, ChainType::Live);
+		assert_eq!(genesis.authorities, vec![authority_keys_from_seed("Alice")]);
+		assert!(genesis.endowments.is_empty());
+		assert!(genesis.vesting.is_empty());
+		assert_eq!(genesis.block_reward, (0, None));
+	}
+
+	#[test]
+	fn rejects_invalid_descriptions() {
+		let invalid = |from: &str, to: &str| {
+			let file: GenesisFile = toml::from_str(&alice_only().replacen(from, to, 1)).unwrap();
+			file.validate().unwrap_err()
+		};
+
+		assert!(invalid(&format!("sudo = \"{}\"", ALICE), "sudo = \"0x1234\"")
+			.contains("expected 32 bytes"));
+		assert!(invalid(&format!("grandpa = \"{}\"", ALICE_GRANDPA), "grandpa = \"nonsense\"")
+			.contains("Invalid grandpa key"));
+		assert!(invalid("balance = 1000", "balance = 1").contains("existential deposit"));
+		assert!(invalid(&format!("\"{}\"]", BOB), &format!("\"{}\"]", ALICE))
+			.contains("Duplicate council member"));
+		assert!(invalid("tokenSymbol = \"UNIT\"", "tokenSymbol = \"DOT\"")
+			.contains("Property `tokenSymbol`"));
+		let vesting_account = |account| format!("account = \"{}\"\nbegin", account);
+		assert!(invalid(&vesting_account(ALICE), &vesting_account(ALICE_GRANDPA))
+			.contains("is not endowed"));
+		assert!(invalid("length = 100", "length = 0").contains("at least one unit per block"));
+		assert!(invalid("liquid = \"1000\"", "liquid = \"1000000000000000000000\"")
+			.contains("at least one unit per block"));
+		assert!(invalid("era_length = 10", "era_length = 0").contains("at least one block long"));
+	}
+
+	#[test]
+	fn rejects_duplicate_session_keys() {
+		let (alice, alice_aura, alice_grandpa) = authority_keys_from_seed("Alice");
+		let (bob, bob_aura, bob_grandpa) = authority_keys_from_seed("Bob");
+		let validate = |bob_aura: &AuraId, bob_grandpa: &GrandpaId| {
+			let toml = format!(
+				r#"
+name = "Test"
+id = "test"
+sudo = "{alice}"
+
+[[authorities]]
+account = "{alice}"
+aura = "{alice_aura}"
+grandpa = "{alice_grandpa}"
+
+[[authorities]]
+account = "{bob}"
+aura = "{bob_aura}"
+grandpa = "{bob_grandpa}"
+"#,
+				alice = hex(&alice),
+				alice_aura = hex(&alice_aura),
+				alice_grandpa = hex(&alice_grandpa),
+				bob = hex(&bob),
+				bob_aura = hex(bob_aura),
+				bob_grandpa = hex(bob_grandpa),
+			);
+			toml::from_str::<GenesisFile>(&toml)
+				.unwrap()
+				.validate()
+				.map(|g| g.authorities.len())
+		};
+
+		assert_eq!(validate(&bob_aura, &bob_grandpa), Ok(2));
+		assert!(validate(&alice_aura, &bob_grandpa).unwrap_err().contains("Duplicate aura key"));
+		assert!(validate(&bob_aura, &alice_grandpa)
+			.unwrap_err()
+			.contains("Duplicate grandpa key"));
+	}
+}
@@ -0,0 +1,597 @@
+//! A keystore whose keys live on a remote signer.
+//!
+//! The signer is reached over HTTP and speaks JSON-RPC 2.0. Public keys, messages and signatures
+//! are exchanged as `0x`-prefixed hex strings, key types as their four character identifier
+//! (e.g. `"aura"`) and crypto schemes as their crypto type id (`"sr25"`, `"ed25"` or `"ecds"`).
+//! The methods a signer has to serve are:
+//!
+//! - `keystore_publicKeys(key_type, crypto) -> [public]`
+//! - `keystore_generate(key_type, crypto, seed?) -> public`
+//! - `keystore_insert(key_type, suri, public) -> null`
+//! - `keystore_hasKeys([[public, key_type]]) -> bool`
+//! - `keystore_sign(key_type, crypto, public, message) -> signature?`
+//! - `keystore_signPrehashed(key_type, public, hash) -> signature?`
+//!
+//! The secret keys never leave the signer, so Aura and GRANDPA keys don't have to be stored on
+//! the validator host.
+//!
+//! The node trusts the signer completely and the signer trusts whoever reaches it: requests carry
+//! no credentials, and `keystore_generate` and `keystore_insert` send seeds and secret URIs. The
+//! signer must therefore only be reachable by the node, and the connection must be private. Remote
+//! signers are only accepted over `https://`, which also authenticates the signer by its
+//! certificate; plain `http://` is only accepted for a signer on a loopback address.
+//!
+//! Requests to the signer block. The async [`CryptoStore`] methods run them on tokio's blocking
+//! thread pool, so that a slow signer doesn't hold up the node's async tasks.
+
+use std::{
+	convert::TryFrom,
+	net::IpAddr,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use async_trait::async_trait;
+use jsonrpc_core::{Id, MethodCall, Output, Params, Version};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use sp_core::{
+	crypto::{CryptoTypeId, CryptoTypePublicPair, KeyTypeId},
+	ecdsa, ed25519, sr25519, Bytes,
+};
+use sp_keystore::{
+	vrf::{VRFSignature, VRFTranscriptData},
+	CryptoStore, Error as TraitError, SyncCryptoStore,
+};
+
+/// How long a single request to the signer may take.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A keystore that forwards every operation to a remote signer. Clones share the connection.
+#[derive(Clone)]
+pub struct RemoteKeystore {
+	url: Arc<str>,
+	agent: ureq::Agent,
+	next_id: Arc<AtomicU64>,
+}
+
+impl RemoteKeystore {
+	/// Create a keystore that talks to the signer at `url`.
+	pub fn new(url: &str) -> Result<Self, &'static str> {
+		if let Some(rest) = url.strip_prefix("http://") {
+			if !is_loopback(rest) {
+				return Err("The remote keystore URL must use https, unless the signer runs on a \
+				            loopback address.")
+			}
+		} else if !url.starts_with("https://") {
+			return Err("The remote keystore URL must use the http or https scheme.")
+		}
+
+		Ok(Self {
+			url: url.into(),
+			agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+			next_id: Arc::new(AtomicU64::new(1)),
+		})
+	}
+
+	/// Run `f` on a clone of the keystore on tokio's blocking thread pool.
+	async fn blocking<R, F>(&self, f: F) -> R
+	where
+		R: Send + 'static,
+		F: FnOnce(&Self) -> R + Send + 'static,
+	{
+		let keystore = self.clone();
+		tokio::task::spawn_blocking(move || f(&keystore)).await.unwrap_or_else(|e| {
+			match e.try_into_panic() {
+				Ok(panic) => std::panic::resume_unwind(panic),
+				Err(e) => panic!("Remote keystore request did not complete: {}", e),
+			}
+		})
+	}
+
+	/// Call `method` on the signer and decode its result.
+	fn call<R: DeserializeOwned>(&self, method: &str, params: Value) -> Result<R, TraitError> {
+		let params = match params {
+			Value::Array(params) => Params::Array(params),
+			_ => Params::None,
+		};
+		let request = MethodCall {
+			jsonrpc: Some(Version::V2),
+			method: method.into(),
+			params,
+			id: Id::Num(self.next_id.fetch_add(1, Ordering::Relaxed)),
+		};
+		let request =
+			serde_json::to_value(&request).map_err(|e| TraitError::Other(e.to_string()))?;
+
+		let output: Output = self
+			.agent
+			.post(&self.url)
+			.send_json(request)
+			.map_err(|e| {
+				log::warn!(target: "keystore", "Remote keystore request `{}` failed: {}", method, e);
+				TraitError::Unavailable
+			})?
+			.into_json()
+			.map_err(|e| {
+				TraitError::Other(format!("Invalid response from remote keystore: {}", e))
+			})?;
+
+		let result: Result<Value, jsonrpc_core::Error> = output.into();
+		let result = result.map_err(|e| TraitError::Other(e.message))?;
+
+		serde_json::from_value(result)
+			.map_err(|e| TraitError::Other(format!("Invalid response from remote keystore: {}", e)))
+	}
+
+	fn public_keys(&self, id: KeyTypeId, crypto: CryptoTypeId) -> Vec<Vec<u8>> {
+		self.call::<Vec<Bytes>>("keystore_publicKeys", json!([key_type(id), crypto_type(crypto)]))
+			.map(|keys| keys.into_iter().map(|key| key.0).collect())
+			.unwrap_or_else(|e| {
+				log::warn!(target: "keystore", "Failed to list remote {:?} keys: {}", id, e);
+				Vec::new()
+			})
+	}
+
+	fn generate_new(
+		&self,
+		id: KeyTypeId,
+		crypto: CryptoTypeId,
+		seed: Option<&str>,
+	) -> Result<Vec<u8>, TraitError> {
+		self.call::<Bytes>("keystore_generate", json!([key_type(id), crypto_type(crypto), seed]))
+			.map(|key| key.0)
+	}
+}
+
+/// Whether the host of a URL, given without its scheme, is `localhost` or a loopback address.
+fn is_loopback(url: &str) -> bool {
+	let authority = url.split(|c| c == '/' || c == '?' || c == '#').next().unwrap_or_default();
+	let host = authority.rsplit('@').next().unwrap_or_default();
+	let host = match host.strip_prefix('[') {
+		// An IPv6 address, e.g. `[::1]:8700`.
+		Some(rest) => rest.split(']').next().unwrap_or_default(),
+		None => host.split(':').next().unwrap_or_default(),
+	};
+	host.eq_ignore_ascii_case("localhost") ||
+		host.parse().map_or(false, |ip: IpAddr| ip.is_loopback())
+}
+
+/// The JSON representation of a key type, e.g. `"aura"`.
+fn key_type(id: KeyTypeId) -> String {
+	String::from_utf8_lossy(&id.0).into_owned()
+}
+
+/// The JSON representation of a crypto type, e.g. `"sr25"`.
+fn crypto_type(id: CryptoTypeId) -> String {
+	String::from_utf8_lossy(&id.0).into_owned()
+}
+
+/// Decode the public keys returned by the signer, dropping any of the wrong length.
+fn decode_keys<P: for<'a> TryFrom<&'a [u8]>>(keys: Vec<Vec<u8>>) -> Vec<P> {
+	keys.iter().filter_map(|key| P::try_from(&key[..]).ok()).collect()
+}
+
+fn decode_key<P: for<'a> TryFrom<&'a [u8]>>(key: Vec<u8>) -> Result<P, TraitError> {
+	P::try_from(&key[..])
+		.map_err(|_| TraitError::ValidationError("Invalid public key from remote keystore".into()))
+}
+
+#[async_trait]
+impl CryptoStore for RemoteKeystore {
+	async fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		self.blocking(move |keystore| SyncCryptoStore::sr25519_public_keys(keystore, id))
+			.await
+	}
+
+	async fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<sr25519::Public, TraitError> {
+		let seed = seed.map(String::from);
+		self.blocking(move |keystore| {
+			SyncCryptoStore::sr25519_generate_new(keystore, id, seed.as_deref())
+		})
+		.await
+	}
+
+	async fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		self.blocking(move |keystore| SyncCryptoStore::ed25519_public_keys(keystore, id))
+			.await
+	}
+
+	async fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ed25519::Public, TraitError> {
+		let seed = seed.map(String::from);
+		self.blocking(move |keystore| {
+			SyncCryptoStore::ed25519_generate_new(keystore, id, seed.as_deref())
+		})
+		.await
+	}
+
+	async fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.blocking(move |keystore| SyncCryptoStore::ecdsa_public_keys(keystore, id))
+			.await
+	}
+
+	async fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ecdsa::Public, TraitError> {
+		let seed = seed.map(String::from);
+		self.blocking(move |keystore| {
+			SyncCryptoStore::ecdsa_generate_new(keystore, id, seed.as_deref())
+		})
+		.await
+	}
+
+	async fn insert_unknown(&self, id: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+		let (suri, public) = (suri.to_owned(), public.to_vec());
+		self.blocking(move |keystore| SyncCryptoStore::insert_unknown(keystore, id, &suri, &public))
+			.await
+	}
+
+	async fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> Result<Vec<CryptoTypePublicPair>, TraitError> {
+		self.blocking(move |keystore| SyncCryptoStore::supported_keys(keystore, id, keys))
+			.await
+	}
+
+	async fn keys(&self, id: KeyTypeId) -> Result<Vec<CryptoTypePublicPair>, TraitError> {
+		self.blocking(move |keystore| SyncCryptoStore::keys(keystore, id)).await
+	}
+
+	async fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		let public_keys = public_keys.to_vec();
+		self.blocking(move |keystore| SyncCryptoStore::has_keys(keystore, &public_keys))
+			.await
+	}
+
+	async fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> Result<Option<Vec<u8>>, TraitError> {
+		let (key, msg) = (key.clone(), msg.to_vec());
+		self.blocking(move |keystore| SyncCryptoStore::sign_with(keystore, id, &key, &msg))
+			.await
+	}
+
+	async fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		transcript_data: VRFTranscriptData,
+	) -> Result<Option<VRFSignature>, TraitError> {
+		// Fails without asking the signer, so it doesn't block.
+		SyncCryptoStore::sr25519_vrf_sign(self, key_type, public, transcript_data)
+	}
+
+	async fn ecdsa_sign_prehashed(
+		&self,
+		id: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Result<Option<ecdsa::Signature>, TraitError> {
+		let (public, msg) = (public.clone(), *msg);
+		self.blocking(move |keystore| {
+			SyncCryptoStore::ecdsa_sign_prehashed(keystore, id, &public, &msg)
+		})
+		.await
+	}
+}
+
+impl SyncCryptoStore for RemoteKeystore {
+	fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		decode_keys(self.public_keys(id, sr25519::CRYPTO_ID))
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<sr25519::Public, TraitError> {
+		decode_key(self.generate_new(id, sr25519::CRYPTO_ID, seed)?)
+	}
+
+	fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		decode_keys(self.public_keys(id, ed25519::CRYPTO_ID))
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ed25519::Public, TraitError> {
+		decode_key(self.generate_new(id, ed25519::CRYPTO_ID, seed)?)
+	}
+
+	fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		decode_keys(self.public_keys(id, ecdsa::CRYPTO_ID))
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ecdsa::Public, TraitError> {
+		decode_key(self.generate_new(id, ecdsa::CRYPTO_ID, seed)?)
+	}
+
+	fn insert_unknown(&self, id: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+		self.call::<()>("keystore_insert", json!([key_type(id), suri, Bytes(public.to_vec())]))
+			.map_err(|e| log::warn!(target: "keystore", "Failed to insert remote key: {}", e))
+	}
+
+	fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> Result<Vec<CryptoTypePublicPair>, TraitError> {
+		let all_keys = SyncCryptoStore::keys(self, id)?;
+		Ok(keys.into_iter().filter(|key| all_keys.contains(key)).collect())
+	}
+
+	fn keys(&self, id: KeyTypeId) -> Result<Vec<CryptoTypePublicPair>, TraitError> {
+		let mut keys = Vec::new();
+		for crypto in [sr25519::CRYPTO_ID, ed25519::CRYPTO_ID, ecdsa::CRYPTO_ID] {
+			let public = self.call::<Vec<Bytes>>(
+				"keystore_publicKeys",
+				json!([key_type(id), crypto_type(crypto)]),
+			)?;
+			keys.extend(public.into_iter().map(|key| CryptoTypePublicPair(crypto, key.0)));
+		}
+		Ok(keys)
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		let public_keys: Vec<_> = public_keys
+			.iter()
+			.map(|(public, id)| json!([Bytes(public.clone()), key_type(*id)]))
+			.collect();
+
+		self.call::<bool>("keystore_hasKeys", json!([public_keys])).unwrap_or_else(|e| {
+			log::warn!(target: "keystore", "Failed to query remote keys: {}", e);
+			false
+		})
+	}
+
+	fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> Result<Option<Vec<u8>>, TraitError> {
+		let signature = self.call::<Option<Bytes>>(
+			"keystore_sign",
+			json!([key_type(id), crypto_type(key.0), Bytes(key.1.clone()), Bytes(msg.to_vec())]),
+		)?;
+		Ok(signature.map(|signature| signature.0))
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &sr25519::Public,
+		_transcript_data: VRFTranscriptData,
+	) -> Result<Option<VRFSignature>, TraitError> {
+		// A VRF transcript can't be sent over the wire. Neither Aura nor GRANDPA needs one.
+		Err(TraitError::Other("VRF signing is not supported by the remote keystore".into()))
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		id: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Result<Option<ecdsa::Signature>, TraitError> {
+		let signature = self.call::<Option<Bytes>>(
+			"keystore_signPrehashed",
+			json!([key_type(id), Bytes(public.0.to_vec()), Bytes(msg.to_vec())]),
+		)?;
+
+		signature
+			.map(|signature| {
+				ecdsa::Signature::try_from(&signature.0[..]).map_err(|_| {
+					TraitError::ValidationError("Invalid signature from remote keystore".into())
+				})
+			})
+			.transpose()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use jsonrpc_core::IoHandler;
+	use jsonrpc_http_server::{Server, ServerBuilder};
+	use sp_core::{
+		crypto::key_types::{AURA, GRANDPA},
+		Pair,
+	};
+	use sp_keystore::testing::KeyStore;
+	use std::sync::Arc;
+
+	fn crypto_id(crypto: &str) -> CryptoTypeId {
+		let mut id = [0u8; 4];
+		id.copy_from_slice(crypto.as_bytes());
+		CryptoTypeId(id)
+	}
+
+	fn key_type_id(id: &str) -> KeyTypeId {
+		KeyTypeId::try_from(id).expect("four character key type")
+	}
+
+	fn invalid(e: impl ToString) -> jsonrpc_core::Error {
+		jsonrpc_core::Error::invalid_params(e.to_string())
+	}
+
+	/// A signer that serves the remote keystore protocol from an in-memory keystore.
+	fn mock_signer() -> Server {
+		let store = Arc::new(KeyStore::new());
+		let mut io = IoHandler::new();
+
+		let keys = store.clone();
+		io.add_sync_method("keystore_publicKeys", move |params: Params| {
+			let (id, crypto): (String, String) = params.parse()?;
+			let id = key_type_id(&id);
+			let public: Vec<Bytes> = match crypto.as_str() {
+				"sr25" => SyncCryptoStore::sr25519_public_keys(&*keys, id)
+					.into_iter()
+					.map(|k| Bytes(k.0.to_vec()))
+					.collect(),
+				"ed25" => SyncCryptoStore::ed25519_public_keys(&*keys, id)
+					.into_iter()
+					.map(|k| Bytes(k.0.to_vec()))
+					.collect(),
+				_ => Vec::new(),
+			};
+			Ok(json!(public))
+		});
+
+		let keys = store.clone();
+		io.add_sync_method("keystore_generate", move |params: Params| {
+			let (id, crypto, seed): (String, String, Option<String>) = params.parse()?;
+			let id = key_type_id(&id);
+			let seed = seed.as_deref();
+			let public = match crypto.as_str() {
+				"sr25" => SyncCryptoStore::sr25519_generate_new(&*keys, id, seed)
+					.map(|k| k.0.to_vec())
+					.map_err(invalid)?,
+				"ed25" => SyncCryptoStore::ed25519_generate_new(&*keys, id, seed)
+					.map(|k| k.0.to_vec())
+					.map_err(invalid)?,
+				other => return Err(invalid(format!("unsupported crypto {}", other))),
+			};
+			Ok(json!(Bytes(public)))
+		});
+
+		let keys = store.clone();
+		io.add_sync_method("keystore_hasKeys", move |params: Params| {
+			let (public_keys,): (Vec<(Bytes, String)>,) = params.parse()?;
+			let public_keys: Vec<_> =
+				public_keys.into_iter().map(|(key, id)| (key.0, key_type_id(&id))).collect();
+			Ok(json!(SyncCryptoStore::has_keys(&*keys, &public_keys)))
+		});
+
+		let keys = store;
+		io.add_sync_method("keystore_sign", move |params: Params| {
+			let (id, crypto, public, msg): (String, String, Bytes, Bytes) = params.parse()?;
+			let key = CryptoTypePublicPair(crypto_id(&crypto), public.0);
+			let signature = SyncCryptoStore::sign_with(&*keys, key_type_id(&id), &key, &msg.0)
+				.map_err(invalid)?;
+			Ok(json!(signature.map(Bytes)))
+		});
+
+		ServerBuilder::new(io)
+			.start_http(&"127.0.0.1:0".parse().expect("valid socket address"))
+			.expect("mock signer starts")
+	}
+
+	fn remote_keystore(server: &Server) -> RemoteKeystore {
+		RemoteKeystore::new(&format!("http://{}", server.address())).expect("valid url")
+	}
+
+	#[test]
+	fn rejects_non_http_urls() {
+		assert!(RemoteKeystore::new("ws://127.0.0.1:9944").is_err());
+	}
+
+	#[test]
+	fn only_accepts_plain_http_on_loopback() {
+		for url in [
+			"https://signer.example.com",
+			"http://127.0.0.1:8700",
+			"http://localhost:8700/rpc",
+			"http://[::1]:8700",
+		] {
+			assert!(RemoteKeystore::new(url).is_ok(), "{} is accepted", url);
+		}
+		for url in [
+			"http://10.0.0.2:8700",
+			"http://signer.example.com",
+			"http://127.0.0.1.example.com",
+			"http://127.0.0.1@signer.example.com",
+		] {
+			assert!(RemoteKeystore::new(url).is_err(), "{} is rejected", url);
+		}
+	}
+
+	#[test]
+	fn generates_and_lists_keys() {
+		let server = mock_signer();
+		let keystore = remote_keystore(&server);
+
+		let aura = SyncCryptoStore::sr25519_generate_new(&keystore, AURA, Some("//Alice")).unwrap();
+		let grandpa =
+			SyncCryptoStore::ed25519_generate_new(&keystore, GRANDPA, Some("//Alice")).unwrap();
+
+		assert_eq!(aura, sr25519::Pair::from_string("//Alice", None).unwrap().public());
+		assert_eq!(SyncCryptoStore::sr25519_public_keys(&keystore, AURA), vec![aura]);
+		assert_eq!(SyncCryptoStore::ed25519_public_keys(&keystore, GRANDPA), vec![grandpa]);
+		assert!(SyncCryptoStore::has_keys(&keystore, &[(aura.0.to_vec(), AURA)]));
+		assert!(!SyncCryptoStore::has_keys(&keystore, &[(aura.0.to_vec(), GRANDPA)]));
+		assert_eq!(
+			SyncCryptoStore::keys(&keystore, AURA).unwrap(),
+			vec![CryptoTypePublicPair(sr25519::CRYPTO_ID, aura.0.to_vec())],
+		);
+	}
+
+	#[test]
+	fn signs_with_remote_keys() {
+		let server = mock_signer();
+		let keystore = remote_keystore(&server);
+		let public = SyncCryptoStore::ed25519_generate_new(&keystore, GRANDPA, None).unwrap();
+		let msg = b"a grandpa vote";
+
+		let signature = SyncCryptoStore::sign_with(
+			&keystore,
+			GRANDPA,
+			&CryptoTypePublicPair(ed25519::CRYPTO_ID, public.0.to_vec()),
+			msg,
+		)
+		.unwrap()
+		.expect("the signer holds the key");
+
+		let signature = ed25519::Signature::try_from(&signature[..]).unwrap();
+		assert!(ed25519::Pair::verify(&signature, msg, &public));
+	}
+
+	#[test]
+	fn async_calls_run_on_the_blocking_pool() {
+		let server = mock_signer();
+		let keystore = remote_keystore(&server);
+		let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+		let aura = runtime
+			.block_on(CryptoStore::sr25519_generate_new(&keystore, AURA, Some("//Alice")))
+			.unwrap();
+		assert_eq!(aura, sr25519::Pair::from_string("//Alice", None).unwrap().public());
+		assert!(runtime.block_on(CryptoStore::has_keys(&keystore, &[(aura.0.to_vec(), AURA)])));
+	}
+
+	#[test]
+	fn unreachable_signer_is_unavailable() {
+		let server = mock_signer();
+		let keystore = remote_keystore(&server);
+		server.close();
+
+		assert!(SyncCryptoStore::sr25519_public_keys(&keystore, AURA).is_empty());
+		assert!(matches!(
+			SyncCryptoStore::sr25519_generate_new(&keystore, AURA, None),
+			Err(TraitError::Unavailable)
+		));
+	}
+}
@@ -1,3 +1,5 @@
 pub mod chain_spec;
+pub mod genesis;
+pub mod keystore;
 pub mod rpc;
 pub mod service;
@@ -2,6 +2,8 @@
 #![warn(missing_docs)]
 
 mod chain_spec;
+mod genesis;
+mod keystore;
 #[macro_use]
 mod service;
 mod cli;
@@ -5,46 +5,194 @@
 
 #![warn(missing_docs)]
 
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
-use node_template_runtime::{opaque::Block, AccountId, Balance, Index};
+use fc_rpc::{
+	EthBlockDataCache, OverrideHandle, RuntimeApiStorageOverride, SchemaV1Override,
+	SchemaV2Override, SchemaV3Override, StorageOverride,
+};
+use fc_rpc_core::types::FeeHistoryCache;
+use fp_storage::EthereumStorageSchema;
+use futures::channel::mpsc;
+use node_template_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Index};
+use sc_client_api::{
+	backend::{AuxStore, Backend, StateBackend, StorageProvider},
+	client::BlockchainEvents,
+};
+use sc_consensus_manual_seal::rpc::{EngineCommand, ManualSeal, ManualSealApi};
+use sc_network::NetworkService;
 pub use sc_rpc_api::DenyUnsafe;
+use sc_transaction_pool::{ChainApi, Pool};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_core::offchain::OffchainStorage;
+use sp_runtime::traits::BlakeTwo256;
+
+/// Dependencies of the `eth_*`, `net_*` and `web3_*` RPCs.
+pub struct EthDeps<A: ChainApi> {
+	/// The transaction pool's validated transactions, to answer for pending ones.
+	pub graph: Arc<Pool<A>>,
+	/// The network service, for `net_*` and the sync state.
+	pub network: Arc<NetworkService<Block, Hash>>,
+	/// Whether the node authors blocks.
+	pub is_authority: bool,
+	/// The database mapping Ethereum hashes to Substrate blocks.
+	pub frontier_backend: Arc<fc_db::Backend<Block>>,
+	/// Reads Ethereum blocks and receipts straight from runtime storage.
+	pub overrides: Arc<OverrideHandle<Block>>,
+	/// Cache of recently requested Ethereum blocks and statuses.
+	pub block_data_cache: Arc<EthBlockDataCache<Block>>,
+	/// The most blocks `eth_feeHistory` reports on.
+	pub fee_history_limit: u64,
+	/// The fee history of recent blocks, kept up to date by a background task.
+	pub fee_history_cache: FeeHistoryCache,
+}
 
 /// Full client dependencies.
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, S, A: ChainApi> {
 	/// The client instance to use.
 	pub client: Arc<C>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Channel to the manual seal authoring task, if the node runs with manual seal.
+	pub command_sink: Option<mpsc::Sender<EngineCommand<Hash>>>,
+	/// The node's offchain database, if the backend has one.
+	pub offchain_storage: Option<S>,
+	/// Dependencies of the Ethereum RPCs.
+	pub eth: EthDeps<A>,
+}
+
+/// Reads Ethereum blocks, receipts and statuses from runtime storage with the layout the runtime
+/// stored them in, falling back to the runtime API for layouts not known here.
+pub fn overrides_handle<C, BE>(client: Arc<C>) -> Arc<OverrideHandle<Block>>
+where
+	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore,
+	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: Send + Sync + 'static,
+	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	let mut overrides_map = BTreeMap::new();
+	overrides_map.insert(
+		EthereumStorageSchema::V1,
+		Box::new(SchemaV1Override::new(client.clone()))
+			as Box<dyn StorageOverride<_> + Send + Sync>,
+	);
+	overrides_map.insert(
+		EthereumStorageSchema::V2,
+		Box::new(SchemaV2Override::new(client.clone()))
+			as Box<dyn StorageOverride<_> + Send + Sync>,
+	);
+	overrides_map.insert(
+		EthereumStorageSchema::V3,
+		Box::new(SchemaV3Override::new(client.clone()))
+			as Box<dyn StorageOverride<_> + Send + Sync>,
+	);
+
+	Arc::new(OverrideHandle {
+		schemas: overrides_map,
+		fallback: Box::new(RuntimeApiStorageOverride::new(client)),
+	})
 }
 
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc::Metadata>
+pub fn create_full<C, P, S, BE, A>(
+	deps: FullDeps<C, P, S, A>,
+) -> jsonrpc_core::IoHandler<sc_rpc::Metadata>
 where
-	C: ProvideRuntimeApi<Block>,
+	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore,
+	C: BlockchainEvents<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
+	C::Api: pallet_template_rpc::TemplateRuntimeApi<Block, AccountId>,
+	C::Api: identity_rpc::IdentityRuntimeApi<Block, AccountId>,
+	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+	C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
 	C::Api: BlockBuilder<Block>,
-	P: TransactionPool + 'static,
+	P: TransactionPool<Block = Block> + 'static,
+	S: OffchainStorage + 'static,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+	A: ChainApi<Block = Block> + 'static,
 {
+	use fc_rpc::{EthApi, EthApiServer, NetApi, NetApiServer, Web3Api, Web3ApiServer};
+	use identity_rpc::{IdentityName, IdentityNameApi};
+	use pallet_contracts_rpc::{Contracts, ContractsApi};
+	use pallet_template_rpc::{Template, TemplateApi, TemplateHistory, TemplateHistoryApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
-	let FullDeps { client, pool, deny_unsafe } = deps;
+	let FullDeps { client, pool, deny_unsafe, command_sink, offchain_storage, eth } = deps;
 
-	io.extend_with(SystemApi::to_delegate(FullSystem::new(client.clone(), pool, deny_unsafe)));
+	io.extend_with(SystemApi::to_delegate(FullSystem::new(
+		client.clone(),
+		pool.clone(),
+		deny_unsafe,
+	)));
 
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
 
+	// `contracts_call`, `contracts_instantiate` and `contracts_getStorage` dry-run contracts
+	// against the state of a block.
+	io.extend_with(ContractsApi::to_delegate(Contracts::new(client.clone())));
+
+	io.extend_with(TemplateApi::to_delegate(Template::new(client.clone())));
+
+	// `identity_displayName` resolves an account to the name it goes by.
+	io.extend_with(IdentityNameApi::to_delegate(IdentityName::new(client.clone())));
+
+	if let Some(storage) = offchain_storage {
+		// `template_history` reads the records the template pallet writes to the offchain index.
+		io.extend_with(TemplateHistoryApi::<AccountId, BlockNumber, Hash>::to_delegate(
+			TemplateHistory::new(client.clone(), storage),
+		));
+	}
+
+	// `eth_*` serves Ethereum tooling: calls, raw transactions, balances, blocks and receipts.
+	// `net_version` and `web3_clientVersion` are what such tooling asks first.
+	let EthDeps {
+		graph,
+		network,
+		is_authority,
+		frontier_backend,
+		overrides,
+		block_data_cache,
+		fee_history_limit,
+		fee_history_cache,
+	} = eth;
+	io.extend_with(EthApiServer::to_delegate(EthApi::new(
+		client.clone(),
+		pool,
+		graph,
+		Some(node_template_runtime::TransactionConverter),
+		network.clone(),
+		// The node holds no Ethereum keys. Transactions are signed by the client.
+		Vec::new(),
+		overrides,
+		frontier_backend,
+		is_authority,
+		block_data_cache,
+		fee_history_limit,
+		fee_history_cache,
+	)));
+	io.extend_with(NetApiServer::to_delegate(NetApi::new(client.clone(), network, true)));
+	io.extend_with(Web3ApiServer::to_delegate(Web3Api::new(client.clone())));
+
+	if let Some(command_sink) = command_sink {
+		// `engine_createBlock` and `engine_finalizeBlock` hand their requests to the manual seal
+		// authoring task.
+		io.extend_with(ManualSealApi::to_delegate(ManualSeal::new(command_sink)));
+	}
+
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
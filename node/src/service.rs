@@ -1,16 +1,34 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
-use node_template_runtime::{self, opaque::Block, RuntimeApi};
-use sc_client_api::ExecutorProvider;
+use crate::{cli::Sealing, keystore::RemoteKeystore};
+use fc_mapping_sync::MappingSyncWorker;
+use fc_rpc::{EthBlockDataCache, EthTask};
+use fc_rpc_core::types::FeeHistoryCache;
+use futures::{channel::mpsc, prelude::*, stream};
+use node_template_runtime::{self, apis::ChainPropertiesApi, opaque::Block, Hash, RuntimeApi};
+use sc_client_api::{Backend, BlockchainEvents, ExecutorProvider};
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
+use sc_consensus_manual_seal::{
+	consensus::{aura::AuraConsensusDataProvider, timestamp::SlotTimestampProvider},
+	EngineCommand, ManualSealParams,
+};
 pub use sc_executor::NativeElseWasmExecutor;
 use sc_finality_grandpa::SharedVoterState;
-use sc_keystore::LocalKeystore;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
+use sc_transaction_pool_api::TransactionPool;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
 use sp_consensus::SlotData;
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
-use std::{sync::Arc, time::Duration};
+use sp_runtime::generic::BlockId;
+use std::{
+	collections::BTreeMap,
+	path::PathBuf,
+	pin::Pin,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
 
 // Our native executor instance.
 pub struct ExecutorDispatch;
@@ -37,8 +55,34 @@ type FullClient =
 type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
 
+/// The most blocks `eth_feeHistory` reports on.
+const FEE_HISTORY_LIMIT: u64 = 2048;
+
+/// The directory of the database mapping Ethereum blocks and transactions to Substrate blocks.
+pub fn frontier_database_dir(config: &Configuration) -> PathBuf {
+	config
+		.base_path
+		.as_ref()
+		.map_or_else(
+			|| sc_service::BasePath::from_project("", "", "node-template").path().to_path_buf(),
+			|base_path| base_path.config_dir(config.chain_spec.id()),
+		)
+		.join("frontier")
+		.join("db")
+}
+
+fn open_frontier_backend(config: &Configuration) -> Result<Arc<fc_db::Backend<Block>>, String> {
+	Ok(Arc::new(fc_db::Backend::<Block>::new(&fc_db::DatabaseSettings {
+		source: fc_db::DatabaseSettingsSrc::RocksDb {
+			path: frontier_database_dir(config),
+			cache_size: 0,
+		},
+	})?))
+}
+
 pub fn new_partial(
 	config: &Configuration,
+	sealing: Option<Sealing>,
 ) -> Result<
 	sc_service::PartialComponents<
 		FullClient,
@@ -55,14 +99,11 @@ pub fn new_partial(
 			>,
 			sc_finality_grandpa::LinkHalf<Block, FullClient, FullSelectChain>,
 			Option<Telemetry>,
+			Arc<fc_db::Backend<Block>>,
 		),
 	>,
 	ServiceError,
 > {
-	if config.keystore_remote.is_some() {
-		return Err(ServiceError::Other(format!("Remote Keystores are not supported.")))
-	}
-
 	let telemetry = config
 		.telemetry_endpoints
 		.clone()
@@ -110,9 +151,17 @@ pub fn new_partial(
 		telemetry.as_ref().map(|x| x.handle()),
 	)?;
 
-	let slot_duration = sc_consensus_aura::slot_duration(&*client)?.slot_duration();
+	let frontier_backend = open_frontier_backend(config)?;
+
+	let import_queue = if sealing.is_some() {
+		sc_consensus_manual_seal::import_queue(
+			Box::new(client.clone()),
+			&task_manager.spawn_essential_handle(),
+			config.prometheus_registry(),
+		)
+	} else {
+		let slot_duration = sc_consensus_aura::slot_duration(&*client)?.slot_duration();
 
-	let import_queue =
 		sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _, _>(ImportQueueParams {
 			block_import: grandpa_block_import.clone(),
 			justification_import: Some(Box::new(grandpa_block_import.clone())),
@@ -135,7 +184,8 @@ pub fn new_partial(
 			registry: config.prometheus_registry(),
 			check_for_equivocation: Default::default(),
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
-		})?;
+		})?
+	};
 
 	Ok(sc_service::PartialComponents {
 		client,
@@ -145,19 +195,22 @@ pub fn new_partial(
 		keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (grandpa_block_import, grandpa_link, telemetry),
+		other: (grandpa_block_import, grandpa_link, telemetry, frontier_backend),
 	})
 }
 
-fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
-	// FIXME: here would the concrete keystore be built,
-	//        must return a concrete type (NOT `LocalKeystore`) that
-	//        implements `CryptoStore` and `SyncCryptoStore`
-	Err("Remote Keystore not supported.")
+fn remote_keystore(url: &String) -> Result<Arc<RemoteKeystore>, &'static str> {
+	RemoteKeystore::new(url).map(Arc::new)
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+///
+/// With `sealing` set, blocks are authored and finalized by manual seal instead of Aura and
+/// GRANDPA.
+pub fn new_full(
+	mut config: Configuration,
+	sealing: Option<Sealing>,
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -166,8 +219,10 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		mut keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (block_import, grandpa_link, mut telemetry),
-	} = new_partial(&config)?;
+		other: (block_import, grandpa_link, mut telemetry, frontier_backend),
+	} = new_partial(&config, sealing)?;
+
+	check_chain_properties(&client, &config.chain_spec.properties());
 
 	if let Some(url) = &config.keystore_remote {
 		match remote_keystore(url) {
@@ -199,6 +254,19 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		})?;
 
 	if config.offchain_worker.enabled {
+		// Give the template pallet's offchain worker a key to sign its transactions with on
+		// development chains. Other chains need one inserted with `author_insertKey`.
+		if config.chain_spec.chain_type() == sc_service::ChainType::Development {
+			sp_keystore::SyncCryptoStore::sr25519_generate_new(
+				&*keystore_container.sync_keystore(),
+				node_template_runtime::pallet_template::KEY_TYPE,
+				Some("//Alice"),
+			)
+			.map_err(|e| {
+				ServiceError::Other(format!("Error inserting the offchain worker key: {}", e))
+			})?;
+		}
+
 		sc_service::build_offchain_workers(
 			&config,
 			task_manager.spawn_handle(),
@@ -214,13 +282,70 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	// The `engine_*` RPC methods feed manual seal through this channel.
+	let (command_sink, commands_stream) = match sealing {
+		Some(_) => {
+			let (sink, stream) = mpsc::channel(1024);
+			(Some(sink), Some(stream))
+		},
+		None => (None, None),
+	};
+
+	// Record which Substrate block each Ethereum block and transaction is in, for the `eth_*`
+	// RPCs to look them up by their Ethereum hashes.
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-mapping-sync-worker",
+		None,
+		MappingSyncWorker::new(
+			client.import_notification_stream(),
+			Duration::from_millis(node_template_runtime::MILLISECS_PER_BLOCK),
+			client.clone(),
+			backend.clone(),
+			frontier_backend.clone(),
+		)
+		.for_each(|()| future::ready(())),
+	);
+
+	let overrides = crate::rpc::overrides_handle(client.clone());
+	let block_data_cache =
+		Arc::new(EthBlockDataCache::new(task_manager.spawn_handle(), overrides.clone(), 50, 50));
+	let fee_history_cache: FeeHistoryCache = Arc::new(Mutex::new(BTreeMap::new()));
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-fee-history",
+		None,
+		EthTask::fee_history_task(
+			client.clone(),
+			overrides.clone(),
+			fee_history_cache.clone(),
+			FEE_HISTORY_LIMIT,
+		),
+	);
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
+		let network = network.clone();
+		let offchain_storage = backend.offchain_storage();
+		let is_authority = role.is_authority();
 
 		Box::new(move |deny_unsafe, _| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: pool.clone(), deny_unsafe };
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+				command_sink: command_sink.clone(),
+				offchain_storage: offchain_storage.clone(),
+				eth: crate::rpc::EthDeps {
+					graph: pool.pool().clone(),
+					network: network.clone(),
+					is_authority,
+					frontier_backend: frontier_backend.clone(),
+					overrides: overrides.clone(),
+					block_data_cache: block_data_cache.clone(),
+					fee_history_limit: FEE_HISTORY_LIMIT,
+					fee_history_cache: fee_history_cache.clone(),
+				},
+			};
 
 			Ok(crate::rpc::create_full(deps))
 		})
@@ -239,6 +364,29 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		telemetry: telemetry.as_mut(),
 	})?;
 
+	if let (Some(sealing), Some(commands_stream)) = (sealing, commands_stream) {
+		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+			task_manager.spawn_handle(),
+			client.clone(),
+			transaction_pool.clone(),
+			prometheus_registry.as_ref(),
+			telemetry.as_ref().map(|x| x.handle()),
+		);
+
+		run_manual_seal(
+			&task_manager,
+			client,
+			transaction_pool,
+			select_chain,
+			proposer_factory,
+			sealing,
+			commands_stream,
+		);
+
+		network_starter.start_network();
+		return Ok(task_manager)
+	}
+
 	if role.is_authority() {
 		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
@@ -336,3 +484,107 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	network_starter.start_network();
 	Ok(task_manager)
 }
+
+/// Warns about token and address properties of the chain spec that disagree with the runtime,
+/// since wallets would then show balances or addresses different from what the chain uses.
+fn check_chain_properties(client: &FullClient, properties: &sc_service::Properties) {
+	let at = BlockId::Hash(client.info().best_hash);
+	let runtime = match client.runtime_api().properties(&at) {
+		Ok(runtime) => runtime,
+		Err(e) => {
+			log::warn!("Unable to read the chain properties from the runtime: {}", e);
+			return
+		},
+	};
+
+	let expected: [(&str, serde_json::Value); 3] = [
+		("tokenSymbol", String::from_utf8_lossy(&runtime.token_symbol).into()),
+		("tokenDecimals", runtime.token_decimals.into()),
+		("ss58Format", runtime.ss58_format.into()),
+	];
+	for (key, value) in expected {
+		match properties.get(key) {
+			Some(found) if found == &value => {},
+			found => log::warn!(
+				"Chain spec property `{}` is {}, but the runtime expects {}",
+				key,
+				found.map_or_else(|| "missing".into(), |f| f.to_string()),
+				value,
+			),
+		}
+	}
+}
+
+type EngineCommandStream = Pin<Box<dyn Stream<Item = EngineCommand<Hash>> + Send>>;
+
+/// Spawns the manual seal authoring task. Blocks sealed by the `sealing` schedule are finalized
+/// straight away, while those requested through the RPC follow the request's `finalize` flag.
+fn run_manual_seal(
+	task_manager: &TaskManager,
+	client: Arc<FullClient>,
+	transaction_pool: Arc<sc_transaction_pool::FullPool<Block, FullClient>>,
+	select_chain: FullSelectChain,
+	proposer_factory: sc_basic_authorship::ProposerFactory<
+		sc_transaction_pool::FullPool<Block, FullClient>,
+		FullBackend,
+		FullClient,
+		sp_consensus::DisableProofRecording,
+	>,
+	sealing: Sealing,
+	rpc_commands: mpsc::Receiver<EngineCommand<Hash>>,
+) {
+	let seal_new_block = || EngineCommand::SealNewBlock {
+		create_empty: true,
+		finalize: true,
+		parent_hash: None,
+		sender: None,
+	};
+
+	let commands_stream: EngineCommandStream = match sealing {
+		Sealing::Manual => Box::pin(rpc_commands),
+		Sealing::Instant => Box::pin(stream::select(
+			rpc_commands,
+			transaction_pool.import_notification_stream().map(move |_| seal_new_block()),
+		)),
+		Sealing::Interval(millis) => Box::pin(stream::select(
+			rpc_commands,
+			stream::unfold((), move |()| async move {
+				futures_timer::Delay::new(Duration::from_millis(millis)).await;
+				Some((seal_new_block(), ()))
+			}),
+		)),
+	};
+
+	let client_clone = client.clone();
+	let create_inherent_data_providers = move |_, ()| {
+		let client = client_clone.clone();
+		async move {
+			// Claim the next Aura slot, so the runtime accepts the block's timestamp and slot.
+			let timestamp = SlotTimestampProvider::new_aura(client)
+				.map_err(|e| format!("Failed to create the timestamp provider: {:?}", e))?;
+			let slot =
+				sp_consensus_aura::inherents::InherentDataProvider::new(timestamp.slot().into());
+
+			Ok((timestamp, slot))
+		}
+	};
+
+	let authorship_future = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+		block_import: client.clone(),
+		env: proposer_factory,
+		client: client.clone(),
+		pool: transaction_pool,
+		commands_stream,
+		select_chain,
+		consensus_data_provider: Some(Box::new(AuraConsensusDataProvider::new(client))),
+		create_inherent_data_providers,
+	});
+
+	// the manual seal authoring task is considered essential, i.e. if it
+	// fails we take down the service with it.
+	task_manager.spawn_essential_handle().spawn_blocking(
+		"manual-seal",
+		Some("block-authoring"),
+		authorship_future,
+	);
+}
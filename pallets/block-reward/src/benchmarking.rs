@@ -0,0 +1,59 @@
+//! Benchmarking setup for pallet-block-reward
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as BlockReward;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::{Currency, EnsureOrigin, Get, OnInitialize};
+
+benchmarks! {
+	set_reward {
+		let reward = T::Currency::minimum_balance();
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, reward)
+	verify {
+		assert_eq!(Pallet::<T>::reward_per_block(), reward);
+	}
+
+	// Switching to rewards per block pays out the era, to new accounts at worst.
+	set_era_length {
+		let a in 0 .. T::MaxAuthors::get();
+		RewardPerBlock::<T>::put(T::Currency::minimum_balance());
+		EraLength::<T>::put(T::BlockNumber::from(10u32));
+		for i in 0 .. a {
+			AuthoredBlocks::<T>::insert(account::<T::AccountId>("author", i, 0), 1);
+		}
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, None)
+	verify {
+		assert_eq!(Pallet::<T>::era_length(), None);
+		assert_eq!(AuthoredBlocks::<T>::iter().count(), 0);
+	}
+
+	// Paying the author of a block as it is initialized, to a new account at worst.
+	on_initialize_reward {
+		RewardPerBlock::<T>::put(T::Currency::minimum_balance());
+		let block = T::BlockNumber::from(1u32);
+	}: {
+		BlockReward::<T>::on_initialize(block);
+	}
+
+	// The last block of an era pays every author counted in it, to new accounts at worst.
+	on_initialize_pay_era {
+		let a in 0 .. T::MaxAuthors::get();
+		RewardPerBlock::<T>::put(T::Currency::minimum_balance());
+		EraLength::<T>::put(T::BlockNumber::from(10u32));
+		for i in 0 .. a {
+			AuthoredBlocks::<T>::insert(account::<T::AccountId>("author", i, 0), 1);
+		}
+		let block = T::BlockNumber::from(10u32);
+	}: {
+		BlockReward::<T>::on_initialize(block);
+	}
+	verify {
+		assert_eq!(AuthoredBlocks::<T>::iter().count(), 0);
+	}
+
+	impl_benchmark_test_suite!(BlockReward, crate::mock::new_test_ext(None), crate::mock::Test);
+}
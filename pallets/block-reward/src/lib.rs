@@ -0,0 +1,217 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Mints a reward for the authors of blocks, so that authorities are paid without staking.
+///
+/// By default the author of each block is paid `RewardPerBlock` as the block is initialized.
+/// With an era length set, the pallet instead counts the blocks each author produces and pays
+/// them all at the end of the era, at the reward per block in force then. Both the reward and
+/// the era length can be changed by a privileged origin.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, FindAuthor, Imbalance},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Saturating, Zero};
+	use sp_std::prelude::*;
+
+	pub use crate::weights::WeightInfo;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency the rewards are minted in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Finds the author of the block being initialized.
+		type FindAuthor: FindAuthor<Self::AccountId>;
+
+		/// Origin allowed to change the reward and the era length.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The most authors an era can have, to weigh paying it out.
+		#[pallet::constant]
+		type MaxAuthors: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// The reward for authoring a block.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_per_block)]
+	pub type RewardPerBlock<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// The number of blocks rewards are paid out after, or `None` to pay them with every block.
+	#[pallet::storage]
+	#[pallet::getter(fn era_length)]
+	pub type EraLength<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+	/// The blocks each author produced in the current era, while rewards are paid per era.
+	#[pallet::storage]
+	#[pallet::getter(fn authored_blocks)]
+	pub type AuthoredBlocks<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub reward_per_block: BalanceOf<T>,
+		pub era_length: Option<T::BlockNumber>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { reward_per_block: Zero::zero(), era_length: None }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			assert!(self.era_length != Some(Zero::zero()), "Eras must be at least one block long");
+			<RewardPerBlock<T>>::put(self.reward_per_block);
+			<EraLength<T>>::set(self.era_length);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A block author was paid a reward. [author, reward]
+		Rewarded(T::AccountId, BalanceOf<T>),
+		/// The rewards of an era were paid out to its authors. [blocks, total]
+		EraPaid(u32, BalanceOf<T>),
+		/// The reward for authoring a block changed. [reward_per_block]
+		RewardSet(BalanceOf<T>),
+		/// Rewards are paid out after this many blocks, or with every block if `None`.
+		/// [era_length]
+		EraLengthSet(Option<T::BlockNumber>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Eras must be at least one block long.
+		ZeroEraLength,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let digest = <frame_system::Pallet<T>>::digest();
+			let author = T::FindAuthor::find_author(
+				digest.logs.iter().filter_map(|item| item.as_pre_runtime()),
+			);
+
+			match Self::era_length() {
+				None => {
+					if let Some(author) = author {
+						Self::reward(&author, Self::reward_per_block());
+					}
+					T::WeightInfo::on_initialize_reward()
+				},
+				Some(length) => {
+					if let Some(author) = author {
+						<AuthoredBlocks<T>>::mutate(author, |blocks| {
+							*blocks = blocks.saturating_add(1)
+						});
+					}
+					if (n % length).is_zero() {
+						T::WeightInfo::on_initialize_pay_era(Self::pay_era())
+					} else {
+						T::DbWeight::get().reads_writes(2, 1)
+					}
+				},
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the reward for authoring a block. Blocks authored in the current era are paid at
+		/// the new reward, too.
+		#[pallet::weight(T::WeightInfo::set_reward())]
+		pub fn set_reward(
+			origin: OriginFor<T>,
+			#[pallet::compact] reward_per_block: BalanceOf<T>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			<RewardPerBlock<T>>::put(reward_per_block);
+			Self::deposit_event(Event::RewardSet(reward_per_block));
+			Ok(())
+		}
+
+		/// Pay rewards out every `era_length` blocks, or with every block if `None`.
+		///
+		/// An era ends at every block number divisible by the era length. Switching to rewards per
+		/// block pays out the blocks counted so far right away.
+		#[pallet::weight(T::WeightInfo::set_era_length(T::MaxAuthors::get()))]
+		pub fn set_era_length(
+			origin: OriginFor<T>,
+			era_length: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(era_length != Some(Zero::zero()), Error::<T>::ZeroEraLength);
+
+			if era_length.is_none() && Self::era_length().is_some() {
+				Self::pay_era();
+			}
+			<EraLength<T>>::set(era_length);
+			Self::deposit_event(Event::EraLengthSet(era_length));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Mint `amount` for `author` and return the amount minted. Nothing is minted if the
+		/// account would be left below the existential deposit.
+		fn reward(author: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+			let minted = T::Currency::deposit_creating(author, amount).peek();
+			if !minted.is_zero() {
+				Self::deposit_event(Event::Rewarded(author.clone(), minted));
+			}
+			minted
+		}
+
+		/// Pay every author counted in the current era for their blocks and start a new era.
+		/// Returns the number of authors paid.
+		fn pay_era() -> u32 {
+			let reward_per_block = Self::reward_per_block();
+			let (mut blocks, mut authors) = (0u32, 0u32);
+			let mut total = BalanceOf::<T>::zero();
+			for (author, authored) in <AuthoredBlocks<T>>::drain() {
+				let minted =
+					Self::reward(&author, reward_per_block.saturating_mul(authored.into()));
+				blocks = blocks.saturating_add(authored);
+				total = total.saturating_add(minted);
+				authors += 1;
+			}
+			Self::deposit_event(Event::EraPaid(blocks, total));
+			authors
+		}
+	}
+}
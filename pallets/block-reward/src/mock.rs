@@ -0,0 +1,130 @@
+use crate as pallet_block_reward;
+use frame_support::{
+	parameter_types,
+	traits::{FindAuthor, GenesisBuild, OnInitialize},
+};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	ConsensusEngineId,
+};
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		BlockReward: pallet_block_reward::{Pallet, Call, Storage, Event<T>, Config<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 10;
+	pub const MaxAuthors: u32 = 3;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	static AUTHOR: RefCell<Option<u64>> = RefCell::new(Some(1));
+}
+
+/// Set the author of the following blocks, or `None` if they have no known author.
+pub fn set_author(author: Option<u64>) {
+	AUTHOR.with(|a| *a.borrow_mut() = author);
+}
+
+/// Reports the author set with [`set_author`].
+pub struct TestAuthor;
+
+impl FindAuthor<u64> for TestAuthor {
+	fn find_author<'a, I>(_digests: I) -> Option<u64>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		AUTHOR.with(|a| *a.borrow())
+	}
+}
+
+impl pallet_block_reward::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type FindAuthor = TestAuthor;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type MaxAuthors = MaxAuthors;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime, with a reward of 100 per block, paid out
+// every `era_length` blocks if given. Account 1 authors all blocks until told otherwise.
+pub fn new_test_ext(era_length: Option<u64>) -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_block_reward::GenesisConfig::<Test> { reward_per_block: 100, era_length }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	set_author(Some(1));
+
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Initialize blocks up to and including block `n`.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		BlockReward::on_initialize(next);
+	}
+}
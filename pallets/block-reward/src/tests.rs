@@ -0,0 +1,120 @@
+use crate::{mock::*, weights::WeightInfo, Error};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use sp_runtime::traits::BadOrigin;
+
+type RewardEvent = crate::Event<Test>;
+
+#[test]
+fn authors_are_rewarded_with_every_block() {
+	new_test_ext(None).execute_with(|| {
+		run_to_block(3);
+		assert_eq!(Balances::free_balance(1), 200);
+		assert_eq!(Balances::total_issuance(), 200);
+		System::assert_last_event(RewardEvent::Rewarded(1, 100).into());
+
+		set_author(Some(2));
+		run_to_block(4);
+		assert_eq!(Balances::free_balance(2), 100);
+	});
+}
+
+#[test]
+fn nothing_is_minted_without_an_author_or_below_the_existential_deposit() {
+	new_test_ext(None).execute_with(|| {
+		set_author(None);
+		run_to_block(2);
+		assert_eq!(Balances::total_issuance(), 0);
+
+		assert_ok!(BlockReward::set_reward(Origin::root(), 5));
+		set_author(Some(1));
+		run_to_block(3);
+		assert_eq!(Balances::total_issuance(), 0);
+		System::assert_last_event(RewardEvent::RewardSet(5).into());
+	});
+}
+
+#[test]
+fn authors_are_paid_for_their_blocks_at_the_end_of_an_era() {
+	new_test_ext(Some(5)).execute_with(|| {
+		run_to_block(3);
+		set_author(Some(2));
+		run_to_block(4);
+		assert_eq!(BlockReward::authored_blocks(1), 2);
+		assert_eq!(BlockReward::authored_blocks(2), 1);
+		assert_eq!(Balances::total_issuance(), 0);
+
+		set_author(Some(1));
+		run_to_block(5);
+		assert_eq!(Balances::free_balance(1), 300);
+		assert_eq!(Balances::free_balance(2), 100);
+		System::assert_has_event(RewardEvent::Rewarded(2, 100).into());
+		System::assert_last_event(RewardEvent::EraPaid(4, 400).into());
+		assert_eq!(BlockReward::authored_blocks(1), 0);
+
+		// The next era starts counting from scratch.
+		run_to_block(10);
+		assert_eq!(Balances::free_balance(1), 800);
+		System::assert_last_event(RewardEvent::EraPaid(5, 500).into());
+	});
+}
+
+#[test]
+fn payouts_are_weighed_with_weight_info() {
+	new_test_ext(None).execute_with(|| {
+		assert_eq!(BlockReward::on_initialize(2), <() as WeightInfo>::on_initialize_reward());
+	});
+
+	new_test_ext(Some(3)).execute_with(|| {
+		BlockReward::on_initialize(2);
+		set_author(Some(2));
+		assert_eq!(BlockReward::on_initialize(3), <() as WeightInfo>::on_initialize_pay_era(2));
+		System::assert_last_event(RewardEvent::EraPaid(2, 200).into());
+	});
+}
+
+#[test]
+fn eras_are_paid_at_the_current_reward() {
+	new_test_ext(Some(5)).execute_with(|| {
+		run_to_block(3);
+		assert_ok!(BlockReward::set_reward(Origin::root(), 50));
+		run_to_block(5);
+		assert_eq!(Balances::free_balance(1), 200);
+	});
+}
+
+#[test]
+fn switching_to_rewards_per_block_pays_the_era_so_far() {
+	new_test_ext(Some(5)).execute_with(|| {
+		run_to_block(3);
+		assert_ok!(BlockReward::set_era_length(Origin::root(), None));
+		System::assert_has_event(RewardEvent::EraPaid(2, 200).into());
+		System::assert_last_event(RewardEvent::EraLengthSet(None).into());
+		assert_eq!(Balances::free_balance(1), 200);
+
+		run_to_block(4);
+		assert_eq!(Balances::free_balance(1), 300);
+
+		assert_ok!(BlockReward::set_era_length(Origin::root(), Some(2)));
+		run_to_block(5);
+		assert_eq!(BlockReward::authored_blocks(1), 1);
+		run_to_block(6);
+		assert_eq!(Balances::free_balance(1), 500);
+	});
+}
+
+#[test]
+fn only_the_admin_origin_changes_the_parameters() {
+	new_test_ext(None).execute_with(|| {
+		assert_noop!(BlockReward::set_reward(Origin::signed(1), 1_000), BadOrigin);
+		assert_noop!(BlockReward::set_era_length(Origin::signed(1), Some(10)), BadOrigin);
+		assert_noop!(
+			BlockReward::set_era_length(Origin::root(), Some(0)),
+			Error::<Test>::ZeroEraLength
+		);
+
+		assert_ok!(BlockReward::set_reward(Origin::root(), 1_000));
+		assert_eq!(BlockReward::reward_per_block(), 1_000);
+		assert_ok!(BlockReward::set_era_length(Origin::root(), Some(10)));
+		assert_eq!(BlockReward::era_length(), Some(10));
+	});
+}
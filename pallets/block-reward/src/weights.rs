@@ -0,0 +1,103 @@
+//! Weights for pallet_block_reward.
+//!
+//! Rounded upper bounds until `scripts/benchmark.sh` is run, which overwrites this file. The
+//! per-author terms of `set_era_length` and `on_initialize_pay_era` assume every author is paid
+//! into a new account.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_block_reward.
+pub trait WeightInfo {
+	fn set_reward() -> Weight;
+	fn set_era_length(a: u32, ) -> Weight;
+	fn on_initialize_reward() -> Weight;
+	fn on_initialize_pay_era(a: u32, ) -> Weight;
+}
+
+/// Weights for pallet_block_reward using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: BlockReward RewardPerBlock (r:0 w:1)
+	fn set_reward() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: BlockReward EraLength (r:1 w:1)
+	// Storage: BlockReward RewardPerBlock (r:1 w:0)
+	// Storage: BlockReward AuthoredBlocks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn set_era_length(a: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((40_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+	}
+	// Storage: BlockReward EraLength (r:1 w:0)
+	// Storage: BlockReward RewardPerBlock (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn on_initialize_reward() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: BlockReward EraLength (r:1 w:0)
+	// Storage: BlockReward AuthoredBlocks (r:2 w:2)
+	// Storage: BlockReward RewardPerBlock (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn on_initialize_pay_era(a: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((40_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: BlockReward RewardPerBlock (r:0 w:1)
+	fn set_reward() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: BlockReward EraLength (r:1 w:1)
+	// Storage: BlockReward RewardPerBlock (r:1 w:0)
+	// Storage: BlockReward AuthoredBlocks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn set_era_length(a: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((40_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+	}
+	// Storage: BlockReward EraLength (r:1 w:0)
+	// Storage: BlockReward RewardPerBlock (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn on_initialize_reward() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: BlockReward EraLength (r:1 w:0)
+	// Storage: BlockReward AuthoredBlocks (r:2 w:2)
+	// Storage: BlockReward RewardPerBlock (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn on_initialize_pay_era(a: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((40_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+	}
+}
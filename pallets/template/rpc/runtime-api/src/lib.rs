@@ -0,0 +1,15 @@
+//! Runtime API definition for the template pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the values stored by `pallet_template`.
+	pub trait TemplateApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// The value stored for `who`, if any.
+		fn get_something(who: AccountId) -> Option<u32>;
+	}
+}
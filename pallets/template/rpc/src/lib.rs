@@ -0,0 +1,345 @@
+//! RPC interface for the template pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::{Codec, Decode, Encode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_template::offchain::{self, HistoryRecord};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::offchain::{OffchainStorage, STORAGE_PREFIX};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor},
+};
+
+pub use pallet_template_rpc_runtime_api::TemplateApi as TemplateRuntimeApi;
+
+/// Template RPC methods.
+#[rpc]
+pub trait TemplateApi<BlockHash, AccountId> {
+	/// Returns the value stored for `who` at the best block.
+	#[rpc(name = "template_getSomething")]
+	fn get_something(&self, who: AccountId) -> Result<Option<u32>>;
+
+	/// Returns the value stored for `who` at the given block.
+	#[rpc(name = "template_getSomethingAt")]
+	fn get_something_at(&self, who: AccountId, at: BlockHash) -> Result<Option<u32>>;
+}
+
+/// Implements the [`TemplateApi`] RPC trait for interacting with the template pallet.
+pub struct Template<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Template<C, Block> {
+	/// Create a new `Template` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to the runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId> TemplateApi<<Block as BlockT>::Hash, AccountId> for Template<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: TemplateRuntimeApi<Block, AccountId>,
+	AccountId: Codec,
+{
+	fn get_something(&self, who: AccountId) -> Result<Option<u32>> {
+		self.get_something_at(who, self.client.info().best_hash)
+	}
+
+	fn get_something_at(&self, who: AccountId, at: <Block as BlockT>::Hash) -> Result<Option<u32>> {
+		let api = self.client.runtime_api();
+
+		api.get_something(&BlockId::hash(at), who).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query the stored value.".into(),
+			data: Some(e.to_string().into()),
+		})
+	}
+}
+
+/// The most entries `template_history` returns at once.
+const MAX_HISTORY_PAGE: u32 = 100;
+
+/// A `SomethingStored` event of the template pallet, read from the offchain index.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry<BlockNumber, BlockHash> {
+	/// The position of the entry in the account's history, counting from zero.
+	pub index: u32,
+	/// The number of the block the value was stored in.
+	pub block_number: BlockNumber,
+	/// The hash of the block the value was stored in.
+	pub block_hash: BlockHash,
+	/// The value it replaced, if any.
+	pub old: Option<u32>,
+	/// The value stored.
+	pub new: u32,
+}
+
+/// RPC methods reading the template pallet's history from the offchain index.
+///
+/// The history is only written while the node runs with offchain indexing enabled, so a node
+/// synced without it only knows the events of blocks imported since. A block's events are written
+/// when its child is imported, so those of the best block are not available yet.
+#[rpc]
+pub trait TemplateHistoryApi<AccountId, BlockNumber, BlockHash> {
+	/// Returns up to `count` entries of the `SomethingStored` history of `who`, starting at the
+	/// `start`th one. At most 100 entries are returned at once, and fewer once the end of the
+	/// history is reached. Entries written by blocks that are not in the best chain are skipped,
+	/// so the indices of the returned entries may have gaps.
+	#[rpc(name = "template_history")]
+	fn history(
+		&self,
+		who: AccountId,
+		start: u32,
+		count: u32,
+	) -> Result<Vec<HistoryEntry<BlockNumber, BlockHash>>>;
+}
+
+/// Implements [`TemplateHistoryApi`] on top of the node's offchain database.
+pub struct TemplateHistory<C, S, Block> {
+	client: Arc<C>,
+	storage: S,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, S, Block> TemplateHistory<C, S, Block> {
+	/// Create a new `TemplateHistory` reading from `storage`, and checking the blocks of its
+	/// entries against the best chain of `client`.
+	pub fn new(client: Arc<C>, storage: S) -> Self {
+		Self { client, storage, _marker: Default::default() }
+	}
+}
+
+impl<C, S, Block> TemplateHistory<C, S, Block>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+{
+	/// Whether `hash` is the hash of the block numbered `number` in the best chain.
+	fn is_canonical(&self, number: NumberFor<Block>, hash: Block::Hash) -> Result<bool> {
+		let canonical = self.client.hash(number).map_err(|e| RpcError {
+			code: ErrorCode::InternalError,
+			message: "Unable to look up a block of the best chain.".into(),
+			data: Some(e.to_string().into()),
+		})?;
+		Ok(canonical == Some(hash))
+	}
+}
+
+impl<C, S, Block, AccountId> TemplateHistoryApi<AccountId, NumberFor<Block>, Block::Hash>
+	for TemplateHistory<C, S, Block>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+	S: OffchainStorage + 'static,
+	AccountId: Encode,
+{
+	fn history(
+		&self,
+		who: AccountId,
+		start: u32,
+		count: u32,
+	) -> Result<Vec<HistoryEntry<NumberFor<Block>, Block::Hash>>> {
+		let end = start.saturating_add(count.min(MAX_HISTORY_PAGE));
+		let mut entries = Vec::new();
+		for index in start..end {
+			let raw = match self.storage.get(STORAGE_PREFIX, &offchain::history_key(&who, index)) {
+				Some(raw) => raw,
+				None => break,
+			};
+			let record = HistoryRecord::<NumberFor<Block>, Block::Hash>::decode(&mut &raw[..])
+				.map_err(|e| RpcError {
+					code: ErrorCode::InternalError,
+					message: "Unable to decode a history record.".into(),
+					data: Some(e.to_string().into()),
+				})?;
+			// Blocks of forks write under the same indices as the best chain.
+			if !self.is_canonical(record.block_number, record.block_hash)? {
+				continue
+			}
+			entries.push(HistoryEntry {
+				index,
+				block_number: record.block_number,
+				block_hash: record.block_hash,
+				old: record.old,
+				new: record.new,
+			});
+		}
+		Ok(entries)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_blockchain::{BlockStatus, Info};
+	use sp_core::{offchain::storage::InMemOffchainStorage, H256};
+	use sp_runtime::testing::{Block as TestBlock, ExtrinsicWrapper};
+
+	type Block = TestBlock<ExtrinsicWrapper<()>>;
+	type AccountId = u64;
+	type BlockNumber = u64;
+	type Api = TemplateHistory<Chain, InMemOffchainStorage, Block>;
+
+	/// The hash of the best chain's block numbered `number`.
+	fn canonical(number: BlockNumber) -> H256 {
+		H256::repeat_byte(number as u8)
+	}
+
+	/// A best chain of ten blocks, with the hashes of [`canonical`].
+	struct Chain;
+
+	impl HeaderBackend<Block> for Chain {
+		fn header(
+			&self,
+			_: BlockId<Block>,
+		) -> sp_blockchain::Result<Option<<Block as BlockT>::Header>> {
+			Ok(None)
+		}
+
+		fn info(&self) -> Info<Block> {
+			unimplemented!("not used by `template_history`")
+		}
+
+		fn status(&self, _: BlockId<Block>) -> sp_blockchain::Result<BlockStatus> {
+			Ok(BlockStatus::Unknown)
+		}
+
+		fn number(&self, hash: H256) -> sp_blockchain::Result<Option<BlockNumber>> {
+			Ok((0..10).find(|number| canonical(*number) == hash))
+		}
+
+		fn hash(&self, number: BlockNumber) -> sp_blockchain::Result<Option<H256>> {
+			Ok((number < 10).then(|| canonical(number)))
+		}
+	}
+
+	fn history(records: &[(AccountId, u32, HistoryRecord<BlockNumber, H256>)]) -> Api {
+		let mut storage = InMemOffchainStorage::default();
+		for (who, index, record) in records {
+			storage.set(STORAGE_PREFIX, &offchain::history_key(who, *index), &record.encode());
+		}
+		TemplateHistory::new(Arc::new(Chain), storage)
+	}
+
+	/// A record of a block of the best chain.
+	fn record(
+		block_number: BlockNumber,
+		old: Option<u32>,
+		new: u32,
+	) -> HistoryRecord<BlockNumber, H256> {
+		HistoryRecord { block_number, block_hash: canonical(block_number), old, new }
+	}
+
+	fn entry(
+		index: u32,
+		block_number: BlockNumber,
+		old: Option<u32>,
+		new: u32,
+	) -> HistoryEntry<BlockNumber, H256> {
+		HistoryEntry { index, block_number, block_hash: canonical(block_number), old, new }
+	}
+
+	fn page(
+		api: &Api,
+		who: AccountId,
+		start: u32,
+		count: u32,
+	) -> Vec<HistoryEntry<BlockNumber, H256>> {
+		TemplateHistoryApi::<AccountId, BlockNumber, H256>::history(api, who, start, count).unwrap()
+	}
+
+	#[test]
+	fn history_is_paged_per_account() {
+		let (alice, bob) = (1, 2);
+		let api = history(&[
+			(alice, 0, record(1, None, 42)),
+			(alice, 1, record(2, Some(42), 43)),
+			(alice, 2, record(5, Some(43), 44)),
+			(bob, 0, record(3, None, 7)),
+		]);
+
+		assert_eq!(page(&api, alice, 1, 1), vec![entry(1, 2, Some(42), 43)]);
+
+		// Paging stops at the end of the history.
+		let entries = page(&api, alice, 1, 10);
+		assert_eq!(entries.iter().map(|e| e.index).collect::<Vec<_>>(), vec![1, 2]);
+		assert!(page(&api, alice, 3, 10).is_empty());
+
+		assert_eq!(page(&api, bob, 0, 10), vec![entry(0, 3, None, 7)]);
+	}
+
+	#[test]
+	fn history_pages_are_bounded() {
+		let alice = 1;
+		let records: Vec<_> =
+			(0..MAX_HISTORY_PAGE + 10).map(|i| (alice, i, record(1, None, i))).collect();
+		let api = history(&records);
+
+		assert_eq!(page(&api, alice, 0, u32::MAX).len(), MAX_HISTORY_PAGE as usize);
+	}
+
+	#[test]
+	fn entries_of_sibling_blocks_off_the_best_chain_are_skipped() {
+		let alice = 1;
+		// Block 3 has a sibling, a child of the same block 2 that lost the fork. Both stored a
+		// value as the second entry of the history, and the sibling was indexed last.
+		let sibling = H256::repeat_byte(0xff);
+		let api = history(&[
+			(alice, 0, record(2, None, 42)),
+			(
+				alice,
+				1,
+				HistoryRecord { block_number: 3, block_hash: sibling, old: Some(42), new: 5 },
+			),
+			(alice, 2, record(4, Some(43), 44)),
+		]);
+
+		assert_eq!(
+			page(&api, alice, 0, 10),
+			vec![entry(0, 2, None, 42), entry(2, 4, Some(43), 44)]
+		);
+
+		// Once the sibling's entry is overwritten by the block of the best chain, it is returned.
+		let api = history(&[
+			(alice, 0, record(2, None, 42)),
+			(alice, 1, record(3, Some(42), 43)),
+			(alice, 2, record(4, Some(43), 44)),
+		]);
+		assert_eq!(page(&api, alice, 1, 1), vec![entry(1, 3, Some(42), 43)]);
+	}
+
+	#[test]
+	fn undecodable_records_are_reported() {
+		let alice = 1;
+		let mut storage = InMemOffchainStorage::default();
+		storage.set(STORAGE_PREFIX, &offchain::history_key(&alice, 0), &[0xff]);
+
+		let api: Api = TemplateHistory::new(Arc::new(Chain), storage);
+		let err = TemplateHistoryApi::<AccountId, BlockNumber, H256>::history(&api, alice, 0, 1)
+			.unwrap_err();
+		assert_eq!(err.code, ErrorCode::InternalError);
+	}
+}
@@ -2,18 +2,120 @@
 
 use super::*;
 
+use crate::offchain::ValuePayload;
 #[allow(unused)]
 use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
-use frame_system::RawOrigin;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get, Hooks, SortedMembers};
+use frame_system::{
+	offchain::{AppCrypto, SignedPayload},
+	RawOrigin,
+};
+use sp_runtime::RuntimeAppPublic;
+use sp_std::vec::Vec;
+
+/// An account with enough funds for a few storage deposits.
+fn funded<T: Config>(who: T::AccountId) -> T::AccountId {
+	let balance = T::Currency::minimum_balance() + T::StorageDeposit::get() * 10u32.into();
+	T::Currency::make_free_balance_be(&who, balance);
+	who
+}
+
+/// A newly generated key of the offchain worker's key type.
+fn new_key<T: Config>() -> T::Public {
+	let key =
+		<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::generate_pair(
+			None,
+		);
+	<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key).into()
+}
 
 benchmarks! {
 	do_something {
-		let s in 0 .. 100;
+		let caller = funded::<T>(whitelisted_caller());
+	}: _(RawOrigin::Signed(caller.clone()), 42)
+	verify {
+		assert_eq!(Pallet::<T>::value_of(&caller), Some(42));
+	}
+
+	clear_something {
+		let caller = funded::<T>(whitelisted_caller());
+		Pallet::<T>::do_something(RawOrigin::Signed(caller.clone()).into(), 42)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Pallet::<T>::value_of(&caller), None);
+	}
+
+	transfer_something {
+		let caller = funded::<T>(whitelisted_caller());
+		let dest = funded::<T>(account("dest", 0, 0));
+		Pallet::<T>::do_something(RawOrigin::Signed(caller.clone()).into(), 42)?;
+	}: _(RawOrigin::Signed(caller.clone()), dest.clone())
+	verify {
+		assert_eq!(Pallet::<T>::value_of(&caller), None);
+		assert_eq!(Pallet::<T>::value_of(&dest), Some(42));
+	}
+
+	cause_error {
+		let caller = funded::<T>(whitelisted_caller());
+		Pallet::<T>::do_something(RawOrigin::Signed(caller.clone()).into(), 42)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Pallet::<T>::value_of(&caller), Some(43));
+	}
+
+	store_values {
+		let v in 0 .. T::MaxValues::get();
+		let caller = funded::<T>(whitelisted_caller());
+		let values: Vec<u32> = (0 .. v).collect();
+		let bounded = values.clone().try_into().expect("at most `MaxValues` values; qed");
+	}: _(RawOrigin::Signed(caller.clone()), bounded)
+	verify {
+		assert_eq!(Pallet::<T>::values_of(&caller), values);
+	}
+
+	clear_values {
+		let v in 0 .. T::MaxValues::get();
+		let caller = funded::<T>(whitelisted_caller());
+		let values = (0 .. v).collect::<Vec<_>>().try_into().expect("at most `MaxValues` values; qed");
+		Pallet::<T>::store_values(RawOrigin::Signed(caller.clone()).into(), values)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Pallet::<T>::values_of(&caller).is_empty());
+	}
+
+	submit_value_signed {
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		T::Authorities::add(&caller);
+	}: _(RawOrigin::Signed(caller), 42)
+	verify {
+		assert_eq!(Pallet::<T>::offchain_value(), Some(42));
+	}
+
+	submit_value_unsigned {
+		let block_number = frame_system::Pallet::<T>::block_number();
+		let public = new_key::<T>();
+		let payload = ValuePayload { block_number, value: 42, public };
+		let signature = SignedPayload::<T>::sign::<T::AuthorityId>(&payload)
+			.expect("the key was just generated; qed");
+	}: _(RawOrigin::None, payload, signature)
+	verify {
+		assert_eq!(Pallet::<T>::offchain_value(), Some(42));
+	}
+
+	// The next block writes the records queued by its parent to the offchain index.
+	index_history {
+		let h in 0 .. 1_000;
+		for i in 0 .. h {
+			let who: T::AccountId = account("who", i, 0);
+			PendingHistory::<T>::append((who, 0u32, Some(i), i + 1));
+		}
+		let block = T::BlockNumber::from(2u32);
+	}: {
+		Template::<T>::on_initialize(block);
+	}
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert!(PendingHistory::<T>::get().is_empty());
 	}
 
 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
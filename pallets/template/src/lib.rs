@@ -14,29 +14,165 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+pub mod offchain;
+pub mod weights;
+
+use sp_core::crypto::KeyTypeId;
+
+/// The key type of the keys the offchain worker signs its transactions with.
+///
+/// Keys of this type can be inserted into the node's keystore with the `author_insertKey` RPC.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"tmpl");
+
+/// The crypto used by the offchain worker to sign transactions.
+///
+/// The runtime sets [`crypto::TemplateAuthId`] as the pallet's `AuthorityId`, which maps the
+/// `sr25519` application key under [`KEY_TYPE`] onto the runtime's `MultiSignature`.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_core::sr25519::{Public as Sr25519Public, Signature as Sr25519Signature};
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// The identifier of the offchain worker's signing keys.
+	pub struct TemplateAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for TemplateAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = Sr25519Signature;
+		type GenericPublic = Sr25519Public;
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::pallet_prelude::*;
-	use frame_system::pallet_prelude::*;
+	use frame_support::{
+		pallet_prelude::*,
+		sp_runtime::traits::{IdentifyAccount, One, Saturating, Zero},
+		traits::{BalanceStatus, Currency, ReservableCurrency, SortedMembers, StorageVersion},
+		transactional,
+	};
+	use frame_system::{
+		offchain::{AppCrypto, CreateSignedTransaction, SignedPayload},
+		pallet_prelude::*,
+	};
+	use sp_std::vec::Vec;
+
+	use crate::offchain::ValuePayload;
+
+	pub use crate::weights::WeightInfo;
+
+	/// The current storage version. Bump it together with a new entry in [`crate::migrations`]
+	/// whenever the layout of a storage item changes.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency in which storage deposits are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved for each storage entry an account creates. It is returned when the
+		/// entry is removed.
+		#[pallet::constant]
+		type StorageDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum number of values an account can store with `store_values`.
+		#[pallet::constant]
+		type MaxValues: Get<u32>;
+
+		/// The identifier of the keys the offchain worker signs its transactions with.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// The accounts that may submit offchain values, either signed with `submit_value_signed`
+		/// or unsigned with a payload signed by their `AuthorityId` key.
+		type Authorities: SortedMembers<Self::AccountId>;
+
+		/// The number of blocks the offchain worker waits between two submissions. It also bounds
+		/// how often an unsigned submission is accepted.
+		#[pallet::constant]
+		type UnsignedInterval: Get<Self::BlockNumber>;
+
+		/// The priority of unsigned transactions submitted by the offchain worker.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	/// A value stored by an account, together with the block it was last written in and the
+	/// deposit reserved for it.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct SomethingRecord<BlockNumber, Balance> {
+		/// The stored value.
+		pub value: u32,
+		/// The block in which the value was last written.
+		pub last_updated: BlockNumber,
+		/// The deposit reserved from the owner of the entry.
+		pub deposit: Balance,
+	}
+
 	// The pallet's runtime storage items.
 	// https://docs.substrate.io/v3/runtime/storage
 	#[pallet::storage]
-	#[pallet::getter(fn something)]
 	// Learn more about declaring storage items:
 	// https://docs.substrate.io/v3/runtime/storage#declaring-storage-items
-	pub type Something<T> = StorageValue<_, u32>;
+	pub type Something<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		SomethingRecord<T::BlockNumber, BalanceOf<T>>,
+	>;
+
+	/// The number of `SomethingStored` records written to the offchain index for an account. The
+	/// records themselves are only available offchain, see [`crate::offchain::history_key`].
+	#[pallet::storage]
+	#[pallet::getter(fn history_count)]
+	pub type HistoryCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The `SomethingStored` records of the current block as `(who, index, old, new)`, written to
+	/// the offchain index by the next block once this block's hash is known.
+	#[pallet::storage]
+	pub type PendingHistory<T: Config> =
+		StorageValue<_, Vec<(T::AccountId, u32, Option<u32>, u32)>, ValueQuery>;
+
+	/// A list of values stored by an account, at most `MaxValues` long, and the deposit reserved
+	/// for it.
+	#[pallet::storage]
+	pub type Values<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		(BalanceOf<T>, BoundedVec<u32, T::MaxValues>),
+	>;
+
+	/// The latest value submitted by an offchain worker.
+	#[pallet::storage]
+	#[pallet::getter(fn offchain_value)]
+	pub type OffchainValue<T> = StorageValue<_, u32>;
+
+	/// The first block in which a new unsigned submission is accepted.
+	#[pallet::storage]
+	#[pallet::getter(fn next_unsigned_at)]
+	pub type NextUnsignedAt<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
@@ -44,8 +180,18 @@ pub mod pallet {
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Event documentation should end with an array that provides descriptive names for event
-		/// parameters. [something, who]
-		SomethingStored(u32, T::AccountId),
+		/// parameters. [who, old, new]
+		SomethingStored(T::AccountId, Option<u32>, u32),
+		/// An account removed its value. [who, old]
+		SomethingCleared(T::AccountId, u32),
+		/// A value was moved from one account to another. [from, to, value]
+		SomethingTransferred(T::AccountId, T::AccountId, u32),
+		/// An account stored a list of values. [who, count]
+		ValuesStored(T::AccountId, u32),
+		/// An account removed its list of values. [who, count]
+		ValuesCleared(T::AccountId, u32),
+		/// An offchain worker submitted a value, signed by an account or unsigned. [who, value]
+		OffchainValueSubmitted(Option<T::AccountId>, u32),
 	}
 
 	// Errors inform users that something went wrong.
@@ -55,6 +201,64 @@ pub mod pallet {
 		NoneValue,
 		/// Errors should have helpful documentation associated with them.
 		StorageOverflow,
+		/// The destination account already holds a value.
+		DestinationOccupied,
+		/// An account cannot transfer its value to itself.
+		TransferToSelf,
+		/// Only the `Authorities` may submit offchain values.
+		NotAuthority,
+		/// Part of the deposit is no longer reserved on the source account, so it cannot move
+		/// along with the value.
+		DepositNotMoved,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Write the `SomethingStored` records of the parent block to the offchain index, now that
+		/// its hash is known.
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let pending = <PendingHistory<T>>::take();
+			let count = pending.len() as u32;
+			if !pending.is_empty() {
+				let block_number = n.saturating_sub(One::one());
+				let block_hash = <frame_system::Pallet<T>>::parent_hash();
+				for (who, index, old, new) in pending {
+					let record =
+						crate::offchain::HistoryRecord { block_number, block_hash, old, new };
+					sp_io::offchain_index::set(
+						&crate::offchain::history_key(&who, index),
+						&record.encode(),
+					);
+				}
+			}
+			T::WeightInfo::index_history(count)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::v1::migrate::<T>()
+				.saturating_add(crate::migrations::v2::migrate::<T>())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			if StorageVersion::get::<Self>() == 0 {
+				crate::migrations::v1::pre_migrate::<T>()?;
+			}
+			crate::migrations::v2::pre_migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			crate::migrations::v1::post_migrate::<T>()?;
+			crate::migrations::v2::post_migrate::<T>()
+		}
+
+		/// Compute a value from offchain storage and an optional HTTP source, then submit it back
+		/// on chain. Runs at most once every `UnsignedInterval` blocks, see
+		/// [`Pallet::run_offchain_worker`].
+		fn offchain_worker(block_number: T::BlockNumber) {
+			Self::run_offchain_worker(block_number);
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -63,40 +267,246 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		/// the caller's storage entry and emits an event. This function must be dispatched by a
+		/// signed extrinsic.
+		///
+		/// Creating the entry reserves `StorageDeposit` from the caller. Overwriting it keeps the
+		/// deposit reserved when it was created.
+		#[pallet::weight(T::WeightInfo::do_something())]
 		pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/origins
 			let who = ensure_signed(origin)?;
 
-			// Update storage.
-			<Something<T>>::put(something);
+			// Update storage, keeping hold of the value being replaced.
+			let old = <Something<T>>::try_mutate(&who, |record| -> Result<_, DispatchError> {
+				let deposit = match record {
+					Some(old) => old.deposit,
+					None => Self::reserve_deposit(&who)?,
+				};
+				Ok(record.replace(Self::record(something, deposit)).map(|old| old.value))
+			})?;
+
+			// Record the change in the offchain index, for indexers to read without replaying
+			// blocks.
+			Self::index_history(&who, old, something);
 
 			// Emit an event.
-			Self::deposit_event(Event::SomethingStored(something, who));
+			Self::deposit_event(Event::SomethingStored(who, old, something));
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
 
+		/// Remove the caller's value from storage and return its deposit.
+		#[pallet::weight(T::WeightInfo::clear_something())]
+		pub fn clear_something(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let old = <Something<T>>::take(&who).ok_or(Error::<T>::NoneValue)?;
+			T::Currency::unreserve(&who, old.deposit);
+
+			Self::deposit_event(Event::SomethingCleared(who, old.value));
+			Ok(())
+		}
+
+		/// Move the caller's value to `dest`. The destination must not already hold a value, so a
+		/// transfer never silently overwrites another account's entry.
+		///
+		/// The deposit moves along with the value and stays reserved on `dest`, which gets it back
+		/// when clearing the value. `dest` must be an existing account. The call fails if any part
+		/// of the deposit cannot be moved, so no reserve is left behind without a record.
+		#[pallet::weight(T::WeightInfo::transfer_something())]
+		#[transactional]
+		pub fn transfer_something(origin: OriginFor<T>, dest: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(who != dest, Error::<T>::TransferToSelf);
+			ensure!(!<Something<T>>::contains_key(&dest), Error::<T>::DestinationOccupied);
+
+			let record = <Something<T>>::get(&who).ok_or(Error::<T>::NoneValue)?;
+			let unmoved = T::Currency::repatriate_reserved(
+				&who,
+				&dest,
+				record.deposit,
+				BalanceStatus::Reserved,
+			)?;
+			// Undone by `transactional` along with the part of the deposit that did move.
+			ensure!(unmoved.is_zero(), Error::<T>::DepositNotMoved);
+			<Something<T>>::remove(&who);
+			<Something<T>>::insert(&dest, Self::record(record.value, record.deposit));
+
+			Self::deposit_event(Event::SomethingTransferred(who, dest, record.value));
+			Ok(())
+		}
+
 		/// An example dispatchable that may throw a custom error.
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		#[pallet::weight(T::WeightInfo::cause_error())]
 		pub fn cause_error(origin: OriginFor<T>) -> DispatchResult {
-			let _who = ensure_signed(origin)?;
+			let who = ensure_signed(origin)?;
 
 			// Read a value from storage.
-			match <Something<T>>::get() {
+			match <Something<T>>::get(&who) {
 				// Return an error if the value has not been set.
 				None => Err(Error::<T>::NoneValue)?,
 				Some(old) => {
 					// Increment the value read from storage; will error in the event of overflow.
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+					let new = old.value.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
 					// Update the value in storage with the incremented result.
-					<Something<T>>::put(new);
+					<Something<T>>::insert(&who, Self::record(new, old.deposit));
 					Ok(())
 				},
 			}
 		}
+
+		/// Store a list of values for the caller, replacing any list stored before. The weight
+		/// grows with the number of values. Lists longer than `MaxValues` don't decode, so they are
+		/// rejected before the call is dispatched.
+		///
+		/// Creating the list reserves `StorageDeposit` from the caller, replacing it keeps the
+		/// deposit reserved when it was created.
+		#[pallet::weight(T::WeightInfo::store_values(values.len() as u32))]
+		pub fn store_values(
+			origin: OriginFor<T>,
+			values: BoundedVec<u32, T::MaxValues>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let count = values.len() as u32;
+			let deposit = match <Values<T>>::get(&who) {
+				Some((deposit, _)) => deposit,
+				None => Self::reserve_deposit(&who)?,
+			};
+			<Values<T>>::insert(&who, (deposit, values));
+
+			Self::deposit_event(Event::ValuesStored(who, count));
+			Ok(())
+		}
+
+		/// Remove the caller's list of values and return its deposit. The call is charged for a
+		/// full list up front and refunded for the values that were actually stored.
+		#[pallet::weight(T::WeightInfo::clear_values(T::MaxValues::get()))]
+		pub fn clear_values(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let (deposit, values) = <Values<T>>::take(&who).ok_or(Error::<T>::NoneValue)?;
+			T::Currency::unreserve(&who, deposit);
+			let count = values.len() as u32;
+
+			Self::deposit_event(Event::ValuesCleared(who, count));
+			Ok(Some(T::WeightInfo::clear_values(count)).into())
+		}
+
+		/// Submit a value computed by an offchain worker, signed by one of the `Authorities`.
+		#[pallet::weight(T::WeightInfo::submit_value_signed())]
+		pub fn submit_value_signed(origin: OriginFor<T>, value: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(T::Authorities::contains(&who), Error::<T>::NotAuthority);
+
+			<OffchainValue<T>>::put(value);
+
+			Self::deposit_event(Event::OffchainValueSubmitted(Some(who), value));
+			Ok(())
+		}
+
+		/// Submit a value computed by an offchain worker in an unsigned transaction, with a payload
+		/// signed by one of the `Authorities`.
+		///
+		/// Such transactions are free, so [`ValidateUnsigned`] only accepts one every
+		/// `UnsignedInterval` blocks, and only with a valid signature of an authority's key.
+		#[pallet::weight(T::WeightInfo::submit_value_unsigned())]
+		pub fn submit_value_unsigned(
+			origin: OriginFor<T>,
+			payload: ValuePayload<T::Public, T::BlockNumber>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let ValuePayload { block_number, value, .. } = payload;
+			<OffchainValue<T>>::put(value);
+			<NextUnsignedAt<T>>::put(block_number.saturating_add(T::UnsignedInterval::get()));
+
+			Self::deposit_event(Event::OffchainValueSubmitted(None, value));
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (payload, signature) = match call {
+				Call::submit_value_unsigned { payload, signature } => (payload, signature),
+				_ => return InvalidTransaction::Call.into(),
+			};
+			let block_number = payload.block_number;
+
+			// Reject submissions made before the interval since the last one has passed.
+			let next_unsigned_at = <NextUnsignedAt<T>>::get();
+			if next_unsigned_at > block_number {
+				return InvalidTransaction::Stale.into()
+			}
+			// Don't accept values computed in blocks we haven't seen yet.
+			if <frame_system::Pallet<T>>::block_number() < block_number {
+				return InvalidTransaction::Future.into()
+			}
+			// Only authorities may submit for free, and only with their own key.
+			if !T::Authorities::contains(&payload.public.clone().into_account()) {
+				return InvalidTransaction::BadSigner.into()
+			}
+			if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+				return InvalidTransaction::BadProof.into()
+			}
+
+			ValidTransaction::with_tag_prefix("TemplateOffchainWorker")
+				.priority(T::UnsignedPriority::get())
+				// Only one submission per interval can make it into the pool.
+				.and_provides(next_unsigned_at)
+				.longevity(5)
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The value currently stored for `who`, if any.
+		pub fn value_of(who: &T::AccountId) -> Option<u32> {
+			<Something<T>>::get(who).map(|record| record.value)
+		}
+
+		/// The list of values currently stored for `who`.
+		pub fn values_of(who: &T::AccountId) -> Vec<u32> {
+			<Values<T>>::get(who).map(|(_, values)| values.into_inner()).unwrap_or_default()
+		}
+
+		/// A record of `value` backed by `deposit`, written in the current block.
+		fn record(
+			value: u32,
+			deposit: BalanceOf<T>,
+		) -> SomethingRecord<T::BlockNumber, BalanceOf<T>> {
+			SomethingRecord {
+				value,
+				last_updated: <frame_system::Pallet<T>>::block_number(),
+				deposit,
+			}
+		}
+
+		/// Queue a `SomethingStored` record for `who` for the offchain index, under the next free
+		/// index of its history. It is written by the next block, see [`PendingHistory`].
+		fn index_history(who: &T::AccountId, old: Option<u32>, new: u32) {
+			let index = <HistoryCount<T>>::mutate(who, |count| {
+				let index = *count;
+				*count = count.saturating_add(1);
+				index
+			});
+			<PendingHistory<T>>::append((who.clone(), index, old, new));
+		}
+
+		/// Reserve the deposit for a new storage entry from `who`.
+		fn reserve_deposit(who: &T::AccountId) -> Result<BalanceOf<T>, DispatchError> {
+			let deposit = T::StorageDeposit::get();
+			T::Currency::reserve(who, deposit)?;
+			Ok(deposit)
+		}
 	}
 }
@@ -0,0 +1,147 @@
+//! Storage migrations for pallet-template.
+//!
+//! Each module migrates the pallet's storage from the previous version to the one it is named
+//! after and is run from `on_runtime_upgrade`. A migration checks the on-chain storage version
+//! first, so it only ever runs once and is a no-op on chains that started at a later version.
+
+use super::*;
+
+/// Migrates `Something` from the single `u32` of the original template to a map of
+/// [`v1::SomethingRecord`]s keyed by account.
+pub mod v1 {
+	use super::*;
+	use codec::{Decode, Encode};
+	use frame_support::{
+		storage::{unhashed, StoragePrefixedMap},
+		traits::{Get, GetStorageVersion, StorageVersion},
+		weights::Weight,
+	};
+
+	/// The layout of a `Something` entry at storage version 1.
+	#[derive(Encode, Decode, Eq, PartialEq, frame_support::RuntimeDebug)]
+	pub struct SomethingRecord<BlockNumber> {
+		/// The stored value.
+		pub value: u32,
+		/// The block in which the value was last written.
+		pub last_updated: BlockNumber,
+	}
+
+	/// The key of the v0 `Something`, a `StorageValue<_, u32>`. It is the prefix of the map that
+	/// replaced it, so the old value is not one of the map's entries.
+	pub fn v0_key<T: Config>() -> [u8; 32] {
+		<Something<T> as StoragePrefixedMap<_>>::final_prefix()
+	}
+
+	/// Removes the v0 value. Anyone could overwrite it and no account owns it, so there is no
+	/// entry of the map to move it to.
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain = Pallet::<T>::on_chain_storage_version();
+		if on_chain != 0 {
+			log::info!(
+				target: "runtime::template",
+				"skipping v1 migration, storage version is {:?}",
+				on_chain,
+			);
+			return T::DbWeight::get().reads(1)
+		}
+
+		if let Some(value) = unhashed::take::<u32>(&v0_key::<T>()) {
+			log::info!(target: "runtime::template", "removed the v0 value {}", value);
+		}
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(2, 2)
+	}
+
+	/// Checks that the v0 value, if there is one, decodes as the `u32` the migration removes.
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+		match unhashed::get_raw(&v0_key::<T>()) {
+			Some(raw) if u32::decode(&mut &raw[..]).is_err() =>
+				Err("the v0 value of `Something` is not a u32"),
+			_ => Ok(()),
+		}
+	}
+
+	/// Checks that the storage version was bumped and that the v0 value is gone.
+	#[cfg(feature = "try-runtime")]
+	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+		if Pallet::<T>::on_chain_storage_version() < 1 {
+			return Err("storage version was not bumped to 1")
+		}
+		if unhashed::exists(&v0_key::<T>()) {
+			return Err("the v0 value of `Something` was not removed")
+		}
+		Ok(())
+	}
+}
+
+/// Adds a deposit to the entries of `Something` and `Values`.
+pub mod v2 {
+	use super::*;
+	#[cfg(feature = "try-runtime")]
+	use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+	use frame_support::{
+		sp_runtime::traits::Zero,
+		traits::{Get, GetStorageVersion, StorageVersion},
+		weights::Weight,
+		BoundedVec,
+	};
+
+	/// Records existing entries with a zero deposit. They were written before deposits were
+	/// required, so nothing is reserved for them; clearing them returns nothing either.
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain = Pallet::<T>::on_chain_storage_version();
+		if on_chain != 1 {
+			log::info!(
+				target: "runtime::template",
+				"skipping v2 migration, storage version is {:?}",
+				on_chain,
+			);
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut translated = 0u64;
+		<Something<T>>::translate::<v1::SomethingRecord<T::BlockNumber>, _>(|_, old| {
+			translated += 1;
+			Some(SomethingRecord {
+				value: old.value,
+				last_updated: old.last_updated,
+				deposit: Zero::zero(),
+			})
+		});
+		<Values<T>>::translate::<BoundedVec<u32, T::MaxValues>, _>(|_, values| {
+			translated += 1;
+			Some((Zero::zero(), values))
+		});
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		log::info!(target: "runtime::template", "migrated {} entries to v2", translated);
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+
+	/// Records the number of entries, so [`post_migrate`] can check none was lost.
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+		if Pallet::<T>::on_chain_storage_version() < 2 {
+			let count = <Something<T>>::iter_keys().count() as u32 +
+				<Values<T>>::iter_keys().count() as u32;
+			Pallet::<T>::set_temp_storage(count, "template::v2::count");
+		}
+		Ok(())
+	}
+
+	/// Checks that the storage version was bumped and that every entry decodes.
+	#[cfg(feature = "try-runtime")]
+	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+		if Pallet::<T>::on_chain_storage_version() != 2 {
+			return Err("storage version was not bumped to 2")
+		}
+		if let Some(count) = Pallet::<T>::get_temp_storage::<u32>("template::v2::count") {
+			if (<Something<T>>::iter().count() + <Values<T>>::iter().count()) as u32 != count {
+				return Err("entries were lost in the v2 migration")
+			}
+		}
+		Ok(())
+	}
+}
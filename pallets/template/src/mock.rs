@@ -1,14 +1,25 @@
 use crate as pallet_template;
-use frame_support::parameter_types;
+use frame_support::{parameter_types, traits::SortedMembers};
 use frame_system as system;
-use sp_core::H256;
+use parking_lot::RwLock;
+use sp_core::{
+	offchain::{
+		testing::{OffchainState, PoolState, TestOffchainExt, TestTransactionPoolExt},
+		OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+	},
+	H256,
+};
 use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
+	transaction_validity::TransactionPriority,
 };
+use std::{cell::RefCell, sync::Arc};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
+/// The extrinsics the offchain worker submits to the test transaction pool.
+pub type Extrinsic = TestXt<Call, ()>;
 
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
@@ -18,7 +29,8 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
 	}
 );
 
@@ -45,7 +57,7 @@ impl system::Config for Test {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -53,11 +65,119 @@ impl system::Config for Test {
 	type OnSetCode = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		_public: UintAuthorityId,
+		_account: u64,
+		nonce: u64,
+	) -> Option<(Call, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+/// Signs the offchain worker's transactions with the keys set by `UintAuthorityId::set_all_keys`.
+pub struct TestAuthId;
+
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
+parameter_types! {
+	pub const StorageDeposit: u64 = 10;
+	pub const MaxValues: u32 = 10;
+	pub const UnsignedInterval: u64 = 5;
+	pub const UnsignedPriority: TransactionPriority = 100;
+}
+
+thread_local! {
+	static AUTHORITIES: RefCell<Vec<u64>> = RefCell::new(vec![2]);
+}
+
+/// The accounts that may submit offchain values. Only account 2, unless benchmarks add more.
+pub struct OffchainAuthorities;
+
+impl SortedMembers<u64> for OffchainAuthorities {
+	fn sorted_members() -> Vec<u64> {
+		AUTHORITIES.with(|v| v.borrow().clone())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add(who: &u64) {
+		AUTHORITIES.with(|v| {
+			let mut members = v.borrow_mut();
+			members.push(*who);
+			members.sort();
+		})
+	}
+}
+
 impl pallet_template::Config for Test {
 	type Event = Event;
+	type Currency = Balances;
+	type StorageDeposit = StorageDeposit;
+	type MaxValues = MaxValues;
+	type AuthorityId = TestAuthId;
+	type Authorities = OffchainAuthorities;
+	type UnsignedInterval = UnsignedInterval;
+	type UnsignedPriority = UnsignedPriority;
+	type WeightInfo = ();
 }
 
-// Build genesis storage according to the mock runtime.
+// Build genesis storage according to the mock runtime. Accounts 1 to 3 start with a balance of
+// 100, account 4 with nothing.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 100), (2, 100), (3, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	t.into()
+}
+
+// Build genesis storage with the offchain worker extensions registered. Returns the state of the
+// transaction pool and of the offchain worker, i.e. its storage and expected HTTP requests.
+pub fn new_offchain_test_ext(
+) -> (sp_io::TestExternalities, Arc<RwLock<PoolState>>, Arc<RwLock<OffchainState>>) {
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+
+	let mut t = new_test_ext();
+	t.register_extension(OffchainDbExt::new(offchain.clone()));
+	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	(t, pool_state, offchain_state)
 }
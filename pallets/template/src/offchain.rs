@@ -0,0 +1,217 @@
+//! The offchain worker of the template pallet, and the history it writes to the offchain index.
+//!
+//! Each run computes a value as the sum of a base read from [`BASE_KEY`] and a number fetched
+//! over HTTP from the URL stored under [`SOURCE_KEY`], both in the node's persistent offchain
+//! storage. The value is submitted back on chain with a key of type [`crate::KEY_TYPE`] from the
+//! keystore, alternating between a signed transaction paying fees in even blocks and a free
+//! unsigned transaction carrying a [`ValuePayload`] signed by the key in odd blocks. Only keys of
+//! the `Authorities` are used; on nodes without one the worker does nothing.
+//!
+//! When the node runs with offchain indexing enabled, every `SomethingStored` event is also written
+//! to the offchain database as a [`HistoryRecord`] under [`history_key`]. The number of records
+//! per account is kept on chain in `HistoryCount`. A block cannot know its own hash, so its records
+//! are kept on chain in `PendingHistory` and written by the next block, which knows it as its
+//! parent. Blocks of forks are indexed too, and write under the same keys as the canonical blocks
+//! they compete with, so a record may come from a block that is not canonical; its `block_hash`
+//! tells them apart.
+
+use crate::{Call, Config, NextUnsignedAt, Pallet};
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{Get, SortedMembers},
+	RuntimeDebug,
+};
+use frame_system::offchain::{
+	AppCrypto, SendSignedTransaction, SendUnsignedTransaction, SignedPayload, Signer, SigningTypes,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	offchain::{
+		http,
+		storage::StorageValueRef,
+		storage_lock::{BlockAndTime, StorageLock},
+		Duration, StorageKind,
+	},
+	traits::{IdentifyAccount, SaturatedConversion, Zero},
+	RuntimeAppPublic,
+};
+use sp_std::vec::Vec;
+
+/// The prefix of the offchain index keys of the `SomethingStored` history.
+pub const HISTORY_PREFIX: &[u8] = b"template::history";
+
+/// The key of the lock that keeps the worker from running more often than `UnsignedInterval`.
+pub const LOCK_KEY: &[u8] = b"template::ocw::lock";
+
+/// The key of the SCALE encoded `u32` the computed value starts from. Defaults to zero.
+pub const BASE_KEY: &[u8] = b"template::ocw::base";
+
+/// The key of the URL, as raw UTF-8 bytes, of an HTTP source whose response body is a decimal
+/// number added to the base. No request is made if it is not set.
+pub const SOURCE_KEY: &[u8] = b"template::ocw::source";
+
+/// A `SomethingStored` event, as written to the offchain index.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct HistoryRecord<BlockNumber, Hash> {
+	/// The number of the block the value was stored in.
+	pub block_number: BlockNumber,
+	/// The hash of the block the value was stored in.
+	pub block_hash: Hash,
+	/// The value it replaced, if any.
+	pub old: Option<u32>,
+	/// The value stored.
+	pub new: u32,
+}
+
+/// A value submitted by an offchain worker in an unsigned transaction. The transaction carries a
+/// signature of the payload by `public`, which must be the key of one of the `Authorities`.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ValuePayload<Public, BlockNumber> {
+	/// The block the value was computed in.
+	pub block_number: BlockNumber,
+	/// The computed value.
+	pub value: u32,
+	/// The key the payload is signed with.
+	pub public: Public,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for ValuePayload<T::Public, T::BlockNumber> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+/// The offchain index key of the `index`th record in the history of `who`, counting from zero.
+pub fn history_key<AccountId: Encode>(who: &AccountId, index: u32) -> Vec<u8> {
+	(HISTORY_PREFIX, who, index).encode()
+}
+
+/// How long the lock is held for at least, on top of `UnsignedInterval` blocks.
+const LOCK_TIMEOUT_MS: u64 = 10_000;
+
+/// How long to wait for the HTTP source to respond.
+const FETCH_TIMEOUT_MS: u64 = 2_000;
+
+impl<T: Config> Pallet<T> {
+	/// Run the offchain worker for `block_number`.
+	///
+	/// The lock is only released early if the run fails, so a successful submission holds it until
+	/// `UnsignedInterval` blocks have passed.
+	pub(crate) fn run_offchain_worker(block_number: T::BlockNumber) {
+		// Only authorities can submit, which most nodes aren't.
+		let authorities = Self::authority_keys();
+		if authorities.is_empty() {
+			log::debug!(target: "runtime::template", "no authority key, skipping offchain worker");
+			return
+		}
+
+		let mut lock =
+			StorageLock::<BlockAndTime<frame_system::Pallet<T>>>::with_block_and_time_deadline(
+				LOCK_KEY,
+				T::UnsignedInterval::get().saturated_into(),
+				Duration::from_millis(LOCK_TIMEOUT_MS),
+			);
+		let guard = match lock.try_lock() {
+			Ok(guard) => guard,
+			Err(_) => {
+				log::debug!(target: "runtime::template", "offchain worker is rate limited");
+				return
+			},
+		};
+
+		let submitted = Self::compute_value().and_then(|value| {
+			if (block_number % 2u32.into()).is_zero() {
+				Self::submit_value_signed(value, authorities)
+			} else {
+				Self::submit_value_unsigned(block_number, value, authorities)
+			}
+		});
+		match submitted {
+			Ok(()) => guard.forget(),
+			Err(e) => log::warn!(target: "runtime::template", "offchain worker failed: {}", e),
+		}
+	}
+
+	/// The base value from offchain storage plus the value fetched from the HTTP source, if any.
+	fn compute_value() -> Result<u32, &'static str> {
+		let base = StorageValueRef::persistent(BASE_KEY)
+			.get::<u32>()
+			.map_err(|_| "the base value is not a SCALE encoded u32")?
+			.unwrap_or_default();
+		let fetched = match sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, SOURCE_KEY)
+		{
+			Some(url) => Self::fetch_value(&url)?,
+			None => 0,
+		};
+
+		Ok(base.saturating_add(fetched))
+	}
+
+	/// Fetch a decimal number from `url`.
+	fn fetch_value(url: &[u8]) -> Result<u32, &'static str> {
+		let url = sp_std::str::from_utf8(url).map_err(|_| "the source URL is not valid UTF-8")?;
+		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(FETCH_TIMEOUT_MS));
+		let pending = http::Request::get(url)
+			.deadline(deadline)
+			.send()
+			.map_err(|_| "failed to send the HTTP request")?;
+		let response = pending
+			.try_wait(deadline)
+			.map_err(|_| "the HTTP request timed out")?
+			.map_err(|_| "the HTTP request failed")?;
+		if response.code != 200 {
+			return Err("the HTTP source did not respond with 200 OK")
+		}
+
+		let body = response.body().collect::<Vec<u8>>();
+		sp_std::str::from_utf8(&body)
+			.ok()
+			.and_then(|body| body.trim().parse().ok())
+			.ok_or("the HTTP response is not a decimal u32")
+	}
+
+	/// Submit `value` in a transaction signed by one of the `authorities` keys.
+	fn submit_value_signed(value: u32, authorities: Vec<T::Public>) -> Result<(), &'static str> {
+		let signer = Signer::<T, T::AuthorityId>::any_account().with_filter(authorities);
+		match signer.send_signed_transaction(|_| Call::submit_value_signed { value }) {
+			Some((_, Ok(()))) => Ok(()),
+			Some((_, Err(()))) => Err("failed to submit the signed transaction"),
+			None => Err("failed to sign the transaction"),
+		}
+	}
+
+	/// Submit `value`, computed in `block_number`, unsigned with a payload signed by one of the
+	/// `authorities` keys.
+	fn submit_value_unsigned(
+		block_number: T::BlockNumber,
+		value: u32,
+		authorities: Vec<T::Public>,
+	) -> Result<(), &'static str> {
+		// The transaction would be rejected as stale anyway.
+		if <NextUnsignedAt<T>>::get() > block_number {
+			return Err("too early to submit an unsigned transaction")
+		}
+		let signer = Signer::<T, T::AuthorityId>::any_account().with_filter(authorities);
+		match signer.send_unsigned_transaction(
+			|account| ValuePayload { block_number, value, public: account.public.clone() },
+			|payload, signature| Call::submit_value_unsigned { payload, signature },
+		) {
+			Some((_, Ok(()))) => Ok(()),
+			Some((_, Err(()))) => Err("failed to submit the unsigned transaction"),
+			None => Err("failed to sign the unsigned transaction's payload"),
+		}
+	}
+
+	/// The keys of type [`crate::KEY_TYPE`] in the keystore that belong to one of the
+	/// `Authorities`.
+	fn authority_keys() -> Vec<T::Public> {
+		<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+			.into_iter()
+			.map(|key| {
+				<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key)
+					.into()
+			})
+			.filter(|public: &T::Public| T::Authorities::contains(&public.clone().into_account()))
+			.collect()
+	}
+}
@@ -1,5 +1,84 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	migrations,
+	mock::*,
+	offchain::{self, HistoryRecord, ValuePayload},
+	weights::WeightInfo,
+	Error, Event as TemplateEvent, HistoryCount, NextUnsignedAt, PendingHistory, Something,
+	SomethingRecord, Values,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::GetDispatchInfo,
+	storage::unhashed,
+	traits::{
+		Get, GetStorageVersion, Hooks, OnRuntimeUpgrade, ReservableCurrency, StorageVersion,
+		UnfilteredDispatchable,
+	},
+	unsigned::ValidateUnsigned,
+	BoundedVec,
+};
+use sp_core::{
+	offchain::{testing::PendingRequest, StorageKind, Timestamp},
+	H256,
+};
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	transaction_validity::{InvalidTransaction, TransactionSource},
+};
+
+/// `values` as the bounded list `store_values` takes.
+fn values(values: Vec<u32>) -> BoundedVec<u32, MaxValues> {
+	values.try_into().expect("at most `MaxValues` values")
+}
+
+/// A `submit_value_unsigned` call of `value` computed in `block_number`, with a payload signed by
+/// `signer`'s key.
+fn unsigned_call(block_number: u64, value: u32, signer: u64) -> crate::Call<Test> {
+	let payload = ValuePayload { block_number, value, public: UintAuthorityId(signer) };
+	let signature = TestSignature(signer, payload.encode());
+	crate::Call::submit_value_unsigned { payload, signature }
+}
+
+fn last_event() -> TemplateEvent<Test> {
+	System::events()
+		.into_iter()
+		.filter_map(|r| if let Event::TemplateModule(inner) = r.event { Some(inner) } else { None })
+		.last()
+		.expect("an event was deposited")
+}
+
+fn expect_value_request(
+	state: &parking_lot::RwLock<sp_core::offchain::testing::OffchainState>,
+	body: &[u8],
+) {
+	state.write().expect_request(PendingRequest {
+		method: "GET".into(),
+		uri: "http://localhost:8000/value".into(),
+		response: Some(body.to_vec()),
+		sent: true,
+		..Default::default()
+	});
+}
+
+fn set_offchain_source(base: u32, url: Option<&[u8]>) {
+	sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, offchain::BASE_KEY, &base.encode());
+	if let Some(url) = url {
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, offchain::SOURCE_KEY, url);
+	}
+}
+
+fn submitted(pool: &parking_lot::RwLock<sp_core::offchain::testing::PoolState>) -> Vec<Extrinsic> {
+	pool.read()
+		.transactions
+		.iter()
+		.map(|tx| Extrinsic::decode(&mut &tx[..]).expect("a valid extrinsic was submitted"))
+		.collect()
+}
+
+fn record(value: u32, last_updated: u64, deposit: u64) -> SomethingRecord<u64, u64> {
+	SomethingRecord { value, last_updated, deposit }
+}
 
 #[test]
 fn it_works_for_default_value() {
@@ -7,7 +86,7 @@ fn it_works_for_default_value() {
 		// Dispatch a signed extrinsic.
 		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
 		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_eq!(TemplateModule::value_of(&1), Some(42));
 	});
 }
 
@@ -18,3 +97,544 @@ fn correct_error_for_none_value() {
 		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
 	});
 }
+
+#[test]
+fn values_are_kept_per_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 7));
+
+		assert_eq!(TemplateModule::value_of(&1), Some(42));
+		assert_eq!(TemplateModule::value_of(&2), Some(7));
+		assert_eq!(TemplateModule::value_of(&3), None);
+	});
+}
+
+#[test]
+fn stored_event_carries_old_and_new_value() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_eq!(last_event(), TemplateEvent::SomethingStored(1, None, 42));
+
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 43));
+		assert_eq!(last_event(), TemplateEvent::SomethingStored(1, Some(42), 43));
+	});
+}
+
+#[test]
+fn cause_error_increments_callers_value() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), u32::MAX));
+
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_eq!(TemplateModule::value_of(&1), Some(43));
+
+		assert_noop!(
+			TemplateModule::cause_error(Origin::signed(2)),
+			Error::<Test>::StorageOverflow
+		);
+	});
+}
+
+#[test]
+fn clear_something_removes_entry() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(TemplateModule::clear_something(Origin::signed(1)), Error::<Test>::NoneValue);
+
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::clear_something(Origin::signed(1)));
+
+		assert_eq!(TemplateModule::value_of(&1), None);
+		assert_eq!(last_event(), TemplateEvent::SomethingCleared(1, 42));
+	});
+}
+
+#[test]
+fn transfer_something_moves_entry() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+
+		assert_ok!(TemplateModule::transfer_something(Origin::signed(1), 2));
+
+		assert_eq!(TemplateModule::value_of(&1), None);
+		assert_eq!(TemplateModule::value_of(&2), Some(42));
+		assert_eq!(last_event(), TemplateEvent::SomethingTransferred(1, 2, 42));
+	});
+}
+
+#[test]
+fn transfer_something_checks_source_and_destination() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::transfer_something(Origin::signed(1), 2),
+			Error::<Test>::NoneValue
+		);
+
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 7));
+
+		assert_noop!(
+			TemplateModule::transfer_something(Origin::signed(1), 1),
+			Error::<Test>::TransferToSelf
+		);
+		assert_noop!(
+			TemplateModule::transfer_something(Origin::signed(1), 2),
+			Error::<Test>::DestinationOccupied
+		);
+	});
+}
+
+#[test]
+fn store_values_replaces_callers_list() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(TemplateModule::store_values(Origin::signed(1), values(vec![1, 2, 3])));
+		assert_eq!(TemplateModule::values_of(&1), vec![1, 2, 3]);
+		assert_eq!(last_event(), TemplateEvent::ValuesStored(1, 3));
+
+		assert_ok!(TemplateModule::store_values(Origin::signed(1), values(vec![4])));
+		assert_eq!(TemplateModule::values_of(&1), vec![4]);
+		assert!(TemplateModule::values_of(&2).is_empty());
+	});
+}
+
+#[test]
+fn store_values_with_too_many_values_does_not_decode() {
+	let max = MaxValues::get() as usize;
+	let call = crate::Call::<Test>::store_values { values: values(vec![0; max]) };
+	let encoded = call.encode();
+	assert_eq!(crate::Call::<Test>::decode(&mut &encoded[..]).unwrap(), call);
+
+	// The call index, followed by one value too many.
+	let mut oversized = encoded[..1].to_vec();
+	oversized.extend(vec![0u32; max + 1].encode());
+	assert!(crate::Call::<Test>::decode(&mut &oversized[..]).is_err());
+}
+
+#[test]
+fn store_values_weight_scales_with_length() {
+	let short =
+		crate::Call::<Test>::store_values { values: values(vec![0; 1]) }.get_dispatch_info();
+	let long =
+		crate::Call::<Test>::store_values { values: values(vec![0; 10]) }.get_dispatch_info();
+
+	assert_eq!(short.weight, <() as WeightInfo>::store_values(1));
+	assert_eq!(long.weight, <() as WeightInfo>::store_values(10));
+	assert!(long.weight > short.weight);
+}
+
+#[test]
+fn clear_values_refunds_unused_weight() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(TemplateModule::clear_values(Origin::signed(1)), Error::<Test>::NoneValue);
+
+		assert_ok!(TemplateModule::store_values(Origin::signed(1), values(vec![1, 2])));
+		let post_info = TemplateModule::clear_values(Origin::signed(1)).unwrap();
+
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::clear_values(2)));
+		assert!(TemplateModule::values_of(&1).is_empty());
+		assert_eq!(last_event(), TemplateEvent::ValuesCleared(1, 2));
+	});
+}
+
+#[test]
+fn records_block_of_last_update() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_eq!(Something::<Test>::get(1), Some(record(42, 3, 10)));
+
+		System::set_block_number(5);
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_eq!(Something::<Test>::get(1), Some(record(43, 5, 10)));
+	});
+}
+
+#[test]
+fn writing_a_value_reserves_deposit_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(Balances::free_balance(1), 90);
+
+		// Overwriting the value keeps the existing deposit.
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 43));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 10);
+
+		assert_ok!(TemplateModule::clear_something(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+	});
+}
+
+#[test]
+fn writing_a_value_requires_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::do_something(Origin::signed(4), 42),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		assert_noop!(
+			TemplateModule::store_values(Origin::signed(4), values(vec![1])),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn transfer_something_moves_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+
+		assert_ok!(TemplateModule::transfer_something(Origin::signed(1), 2));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 90);
+		assert_eq!(Balances::reserved_balance(2), 10);
+
+		assert_ok!(TemplateModule::clear_something(Origin::signed(2)));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 110);
+	});
+}
+
+#[test]
+fn transfer_something_fails_if_the_deposit_cannot_move() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		// Part of the deposit was released by something other than this pallet.
+		Balances::unreserve(&1, 4);
+
+		assert_noop!(
+			TemplateModule::transfer_something(Origin::signed(1), 2),
+			Error::<Test>::DepositNotMoved
+		);
+		assert_eq!(Balances::reserved_balance(1), 6);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(TemplateModule::value_of(&1), Some(42));
+	});
+}
+
+#[test]
+fn storing_values_reserves_deposit_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::store_values(Origin::signed(1), values(vec![1, 2, 3])));
+		assert_ok!(TemplateModule::store_values(Origin::signed(1), values(vec![4])));
+		assert_eq!(Balances::reserved_balance(1), 10);
+
+		assert_ok!(TemplateModule::clear_values(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+	});
+}
+
+#[test]
+fn v1_migration_removes_the_v0_value() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<TemplateModule>();
+		// The v0 pallet kept a single `u32` in a `StorageValue`.
+		unhashed::put(&migrations::v1::v0_key::<Test>(), &42u32);
+		assert_eq!(Something::<Test>::iter().count(), 0);
+
+		migrations::v1::migrate::<Test>();
+
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+		assert_eq!(unhashed::get::<u32>(&migrations::v1::v0_key::<Test>()), None);
+	});
+}
+
+#[test]
+fn v1_migration_only_runs_once() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<TemplateModule>();
+		System::set_block_number(3);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+
+		System::set_block_number(10);
+		migrations::v1::migrate::<Test>();
+		migrations::v2::migrate::<Test>();
+
+		assert_eq!(Something::<Test>::get(1), Some(record(42, 3, 10)));
+	});
+}
+
+#[test]
+fn v2_migration_grandfathers_entries_without_deposit() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<TemplateModule>();
+		// Entries as written by the v1 pallet, before deposits were taken.
+		unhashed::put(
+			&Something::<Test>::hashed_key_for(1),
+			&migrations::v1::SomethingRecord { value: 42, last_updated: 3u64 },
+		);
+		unhashed::put(&Values::<Test>::hashed_key_for(1), &vec![1u32, 2]);
+
+		migrations::v2::migrate::<Test>();
+
+		assert_eq!(TemplateModule::on_chain_storage_version(), 2);
+		assert_eq!(Something::<Test>::get(1), Some(record(42, 3, 0)));
+		assert_eq!(TemplateModule::values_of(&1), vec![1, 2]);
+
+		// Nothing was reserved for the old entries, so clearing them returns nothing.
+		assert_ok!(TemplateModule::clear_something(Origin::signed(1)));
+		assert_ok!(TemplateModule::clear_values(Origin::signed(1)));
+		assert_eq!(Balances::free_balance(1), 100);
+	});
+}
+
+#[test]
+fn runtime_upgrade_migrates_from_v0() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<TemplateModule>();
+		unhashed::put(&migrations::v1::v0_key::<Test>(), &42u32);
+
+		TemplateModule::on_runtime_upgrade();
+
+		assert_eq!(TemplateModule::on_chain_storage_version(), 2);
+		assert_eq!(unhashed::get::<u32>(&migrations::v1::v0_key::<Test>()), None);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 7));
+		assert_eq!(TemplateModule::value_of(&1), Some(7));
+	});
+}
+
+#[test]
+fn stored_values_are_indexed_offchain_by_the_next_block() {
+	// Start block `n` as a child of a block with hash `parent`.
+	let start_block = |n: u64, parent: H256| {
+		System::set_block_number(n);
+		System::set_parent_hash(parent);
+		TemplateModule::on_initialize(n);
+	};
+	let (block_2, block_3) = (H256::repeat_byte(2), H256::repeat_byte(3));
+
+	let mut t = new_test_ext();
+	t.execute_with(|| {
+		start_block(2, H256::repeat_byte(1));
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 7));
+		assert_eq!(PendingHistory::<Test>::get().len(), 2);
+
+		start_block(3, block_2);
+		assert!(PendingHistory::<Test>::get().is_empty());
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 43));
+
+		start_block(4, block_3);
+		assert_eq!(HistoryCount::<Test>::get(1), 2);
+		assert_eq!(HistoryCount::<Test>::get(2), 1);
+	});
+	t.persist_offchain_overlay();
+
+	let db = t.offchain_db();
+	let history = |who: u64, index| {
+		db.get(&offchain::history_key(&who, index))
+			.map(|record| HistoryRecord::<u64, H256>::decode(&mut &record[..]).unwrap())
+	};
+	let record = |block_number, block_hash, old, new| {
+		Some(HistoryRecord { block_number, block_hash, old, new })
+	};
+	assert_eq!(history(1, 0), record(2, block_2, None, 42));
+	assert_eq!(history(1, 1), record(3, block_3, Some(42), 43));
+	assert_eq!(history(1, 2), None);
+	assert_eq!(history(2, 0), record(2, block_2, None, 7));
+}
+
+#[test]
+fn offchain_worker_submits_unsigned_value_in_odd_blocks() {
+	let (mut t, pool, offchain) = new_offchain_test_ext();
+	expect_value_request(&offchain, b"5\n");
+	// Account 1 is not an authority, so its key is not used.
+	UintAuthorityId::set_all_keys(vec![1, 2]);
+
+	t.execute_with(|| {
+		System::set_block_number(1);
+		set_offchain_source(2, Some(b"http://localhost:8000/value"));
+
+		TemplateModule::offchain_worker(1);
+
+		let txs = submitted(&pool);
+		assert_eq!(txs.len(), 1);
+		assert_eq!(txs[0].signature, None);
+		assert_eq!(txs[0].call, Call::TemplateModule(unsigned_call(1, 7, 2)));
+	});
+}
+
+#[test]
+fn offchain_worker_submits_signed_value_in_even_blocks() {
+	let (mut t, pool, _) = new_offchain_test_ext();
+	// Account 1 is not an authority, so its key is not used.
+	UintAuthorityId::set_all_keys(vec![1, 2]);
+
+	t.execute_with(|| {
+		System::set_block_number(2);
+		set_offchain_source(3, None);
+
+		TemplateModule::offchain_worker(2);
+
+		let txs = submitted(&pool);
+		assert_eq!(txs.len(), 1);
+		assert_eq!(txs[0].signature, Some((0, ())));
+		assert_eq!(
+			txs[0].call,
+			Call::TemplateModule(crate::Call::submit_value_signed { value: 3 })
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_does_nothing_without_authority_key() {
+	let (mut t, pool, _) = new_offchain_test_ext();
+
+	t.execute_with(|| {
+		System::set_block_number(1);
+		TemplateModule::offchain_worker(1);
+		assert!(submitted(&pool).is_empty());
+
+		UintAuthorityId::set_all_keys(vec![1]);
+		TemplateModule::offchain_worker(1);
+		assert!(submitted(&pool).is_empty());
+	});
+}
+
+#[test]
+fn offchain_worker_is_rate_limited() {
+	let (mut t, pool, offchain) = new_offchain_test_ext();
+	UintAuthorityId::set_all_keys(vec![2]);
+
+	t.execute_with(|| {
+		System::set_block_number(1);
+		TemplateModule::offchain_worker(1);
+		assert_eq!(submitted(&pool).len(), 1);
+
+		// The lock is held for `UnsignedInterval` blocks...
+		System::set_block_number(6);
+		TemplateModule::offchain_worker(6);
+		assert_eq!(submitted(&pool).len(), 1);
+
+		// ...and until its timeout has passed.
+		System::set_block_number(7);
+		TemplateModule::offchain_worker(7);
+		assert_eq!(submitted(&pool).len(), 1);
+
+		offchain.write().timestamp = Timestamp::from_unix_millis(10_001);
+		TemplateModule::offchain_worker(7);
+		assert_eq!(submitted(&pool).len(), 2);
+	});
+}
+
+#[test]
+fn failed_offchain_worker_run_releases_lock() {
+	let (mut t, pool, offchain) = new_offchain_test_ext();
+	expect_value_request(&offchain, b"five");
+	expect_value_request(&offchain, b"5");
+	UintAuthorityId::set_all_keys(vec![2]);
+
+	t.execute_with(|| {
+		System::set_block_number(1);
+		set_offchain_source(0, Some(b"http://localhost:8000/value"));
+
+		TemplateModule::offchain_worker(1);
+		assert!(submitted(&pool).is_empty());
+
+		TemplateModule::offchain_worker(1);
+		let txs = submitted(&pool);
+		assert_eq!(txs.len(), 1);
+		assert_eq!(txs[0].call, Call::TemplateModule(unsigned_call(1, 5, 2)));
+	});
+}
+
+#[test]
+fn submitted_values_are_stored() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(TemplateModule::submit_value_signed(Origin::signed(2), 42));
+		assert_eq!(TemplateModule::offchain_value(), Some(42));
+		assert_eq!(last_event(), TemplateEvent::OffchainValueSubmitted(Some(2), 42));
+
+		let call = unsigned_call(1, 43, 2);
+		assert_noop!(
+			call.clone().dispatch_bypass_filter(Origin::signed(1)),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_ok!(call.dispatch_bypass_filter(Origin::none()));
+		assert_eq!(TemplateModule::offchain_value(), Some(43));
+		assert_eq!(TemplateModule::next_unsigned_at(), 1 + UnsignedInterval::get());
+		assert_eq!(last_event(), TemplateEvent::OffchainValueSubmitted(None, 43));
+	});
+}
+
+#[test]
+fn signed_submissions_must_come_from_an_authority() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::submit_value_signed(Origin::signed(1), 42),
+			Error::<Test>::NotAuthority
+		);
+		assert_eq!(TemplateModule::offchain_value(), None);
+	});
+}
+
+#[test]
+fn unsigned_submissions_are_validated() {
+	new_test_ext().execute_with(|| {
+		let validate = |call: crate::Call<Test>| {
+			TemplateModule::validate_unsigned(TransactionSource::External, &call)
+		};
+		System::set_block_number(10);
+		NextUnsignedAt::<Test>::put(8);
+
+		assert_eq!(validate(unsigned_call(7, 42, 2)), InvalidTransaction::Stale.into());
+		assert_eq!(validate(unsigned_call(11, 42, 2)), InvalidTransaction::Future.into());
+
+		let valid = validate(unsigned_call(10, 42, 2)).unwrap();
+		assert_eq!(valid.priority, UnsignedPriority::get());
+		assert_eq!(valid.provides, vec![("TemplateOffchainWorker", 8u64).encode()]);
+
+		let other = crate::Call::submit_value_signed { value: 42 };
+		assert_eq!(validate(other), InvalidTransaction::Call.into());
+	});
+}
+
+#[test]
+fn unsigned_submissions_must_be_signed_by_an_authority() {
+	new_test_ext().execute_with(|| {
+		let validate = |payload: ValuePayload<UintAuthorityId, u64>, signature| {
+			let call = crate::Call::submit_value_unsigned { payload, signature };
+			TemplateModule::validate_unsigned(TransactionSource::External, &call)
+		};
+		System::set_block_number(10);
+		let payload = ValuePayload { block_number: 10, value: 42, public: UintAuthorityId(2) };
+
+		// Account 1 signs validly, but is not an authority.
+		let mut forged = payload.clone();
+		forged.public = UintAuthorityId(1);
+		assert_eq!(
+			validate(forged.clone(), TestSignature(1, forged.encode())),
+			InvalidTransaction::BadSigner.into()
+		);
+
+		// The payload claims to come from authority 2, but is signed by someone else...
+		assert_eq!(
+			validate(payload.clone(), TestSignature(1, payload.encode())),
+			InvalidTransaction::BadProof.into()
+		);
+		// ...or the signature is of a different payload.
+		let mut tampered = payload.clone();
+		tampered.value = 43;
+		assert_eq!(
+			validate(tampered, TestSignature(2, payload.encode())),
+			InvalidTransaction::BadProof.into()
+		);
+
+		assert_ok!(validate(payload.clone(), TestSignature(2, payload.encode())));
+	});
+}
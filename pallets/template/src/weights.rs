@@ -0,0 +1,160 @@
+//! Weights for pallet_template.
+//!
+//! Rounded upper bounds until `scripts/benchmark.sh` is run, which overwrites this file.
+//! `store_values` and `clear_values` grow with the number of values `v` stored for the caller,
+//! and `clear_values` refunds what a shorter list didn't use.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_template.
+pub trait WeightInfo {
+	fn do_something() -> Weight;
+	fn clear_something() -> Weight;
+	fn transfer_something() -> Weight;
+	fn cause_error() -> Weight;
+	fn store_values(v: u32, ) -> Weight;
+	fn clear_values(v: u32, ) -> Weight;
+	fn submit_value_signed() -> Weight;
+	fn submit_value_unsigned() -> Weight;
+	fn index_history(h: u32, ) -> Weight;
+}
+
+/// Weights for pallet_template using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule HistoryCount (r:1 w:1)
+	// Storage: TemplateModule PendingHistory (r:0 w:1)
+	fn do_something() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear_something() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TemplateModule Something (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	fn transfer_something() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: TemplateModule Something (r:1 w:1)
+	fn cause_error() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule Values (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn store_values(v: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((100_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TemplateModule Values (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear_values(v: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((100_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TemplateModule OffchainValue (r:0 w:1)
+	fn submit_value_signed() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule OffchainValue (r:0 w:1)
+	// Storage: TemplateModule NextUnsignedAt (r:0 w:1)
+	fn submit_value_unsigned() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TemplateModule PendingHistory (r:1 w:1)
+	fn index_history(h: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((2_000_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule HistoryCount (r:1 w:1)
+	// Storage: TemplateModule PendingHistory (r:0 w:1)
+	fn do_something() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear_something() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TemplateModule Something (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	fn transfer_something() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: TemplateModule Something (r:1 w:1)
+	fn cause_error() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule Values (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn store_values(v: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((100_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TemplateModule Values (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear_values(v: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((100_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TemplateModule OffchainValue (r:0 w:1)
+	fn submit_value_signed() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule OffchainValue (r:0 w:1)
+	// Storage: TemplateModule NextUnsignedAt (r:0 w:1)
+	fn submit_value_unsigned() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TemplateModule PendingHistory (r:1 w:1)
+	fn index_history(h: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((2_000_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
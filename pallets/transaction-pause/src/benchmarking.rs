@@ -0,0 +1,57 @@
+//! Benchmarking setup for pallet-transaction-pause
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as TransactionPause;
+use frame_benchmarking::benchmarks;
+use frame_support::{dispatch::GetCallMetadata, traits::EnsureOrigin};
+use sp_std::vec::Vec;
+
+/// The name of a pallet of the runtime that may be paused, and of one of its calls.
+fn pausable<T: Config>() -> (Vec<u8>, Vec<u8>) {
+	let pallet = <T as Config>::Call::get_module_names()
+		.iter()
+		.find(|name| !Pallet::<T>::is_unpausable(name.as_bytes()))
+		.expect("the runtime has a pallet that may be paused; qed");
+	let call = <T as Config>::Call::get_call_names(pallet)[0];
+	(pallet.as_bytes().to_vec(), call.as_bytes().to_vec())
+}
+
+benchmarks! {
+	pause_pallet {
+		let (pallet, _) = pausable::<T>();
+		let origin = T::PauseOrigin::successful_origin();
+	}: _<T::Origin>(origin, pallet.clone())
+	verify {
+		assert!(PausedPallets::<T>::contains_key(&pallet));
+	}
+
+	resume_pallet {
+		let (pallet, _) = pausable::<T>();
+		PausedPallets::<T>::insert(&pallet, ());
+		let origin = T::PauseOrigin::successful_origin();
+	}: _<T::Origin>(origin, pallet.clone())
+	verify {
+		assert!(!PausedPallets::<T>::contains_key(&pallet));
+	}
+
+	pause_call {
+		let (pallet, call) = pausable::<T>();
+		let origin = T::PauseOrigin::successful_origin();
+	}: _<T::Origin>(origin, pallet.clone(), call.clone())
+	verify {
+		assert!(PausedCalls::<T>::contains_key((&pallet, &call)));
+	}
+
+	resume_call {
+		let (pallet, call) = pausable::<T>();
+		PausedCalls::<T>::insert((&pallet, &call), ());
+		let origin = T::PauseOrigin::successful_origin();
+	}: _<T::Origin>(origin, pallet.clone(), call.clone())
+	verify {
+		assert!(!PausedCalls::<T>::contains_key((&pallet, &call)));
+	}
+
+	impl_benchmark_test_suite!(TransactionPause, crate::mock::new_test_ext(), crate::mock::Test);
+}
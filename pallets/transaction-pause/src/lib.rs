@@ -0,0 +1,197 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Pauses whole pallets or single calls of the runtime, so that a faulty call can be switched
+/// off without a runtime upgrade.
+///
+/// Pallets and calls are named as in the runtime metadata, for example `TemplateModule` and
+/// `do_something`. [`NonPausedCalls`] is meant to be the runtime's `BaseCallFilter`, which
+/// makes paused calls fail with `CallFiltered` when dispatched from any origin but root. This
+/// pallet's own calls can never be paused, so that paused calls can always be resumed.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::{CallMetadata, GetCallMetadata},
+		pallet_prelude::*,
+		traits::{Contains, PalletInfoAccess},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_std::{marker::PhantomData, prelude::*};
+
+	pub use crate::weights::WeightInfo;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The runtime's call type, to look up the names of its pallets and calls.
+		type Call: GetCallMetadata + IsType<<Self as frame_system::Config>::Call>;
+
+		/// Origin allowed to pause and resume pallets and calls.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Pallets that can't be paused, nor any of their calls.
+		type UnpausablePallets: Contains<Vec<u8>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// The pallets all calls of which are paused.
+	#[pallet::storage]
+	pub type PausedPallets<T: Config> = StorageMap<_, Twox64Concat, Vec<u8>, (), OptionQuery>;
+
+	/// The calls that are paused on their own, by pallet and call name.
+	#[pallet::storage]
+	pub type PausedCalls<T: Config> =
+		StorageMap<_, Twox64Concat, (Vec<u8>, Vec<u8>), (), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// All calls of a pallet are paused. [pallet_name]
+		PalletPaused(Vec<u8>),
+		/// The calls of a pallet are no longer paused, unless paused on their own. [pallet_name]
+		PalletResumed(Vec<u8>),
+		/// A call is paused. [pallet_name, call_name]
+		CallPaused(Vec<u8>, Vec<u8>),
+		/// A call is no longer paused, unless its whole pallet is. [pallet_name, call_name]
+		CallResumed(Vec<u8>, Vec<u8>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The pallet is unpausable.
+		CannotPause,
+		/// The runtime has no pallet with calls of that name.
+		UnknownPallet,
+		/// The pallet has no call of that name.
+		UnknownCall,
+		/// The pallet or call is paused already.
+		AlreadyPaused,
+		/// The pallet or call is not paused.
+		NotPaused,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pause all calls of `pallet_name`.
+		#[pallet::weight(T::WeightInfo::pause_pallet())]
+		pub fn pause_pallet(origin: OriginFor<T>, pallet_name: Vec<u8>) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			Self::pausable_pallet(&pallet_name)?;
+			ensure!(!<PausedPallets<T>>::contains_key(&pallet_name), Error::<T>::AlreadyPaused);
+
+			<PausedPallets<T>>::insert(&pallet_name, ());
+			Self::deposit_event(Event::PalletPaused(pallet_name));
+			Ok(())
+		}
+
+		/// Resume the calls of `pallet_name` paused with `pause_pallet`. Calls paused on their
+		/// own stay paused.
+		#[pallet::weight(T::WeightInfo::resume_pallet())]
+		pub fn resume_pallet(origin: OriginFor<T>, pallet_name: Vec<u8>) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(<PausedPallets<T>>::take(&pallet_name).is_some(), Error::<T>::NotPaused);
+
+			Self::deposit_event(Event::PalletResumed(pallet_name));
+			Ok(())
+		}
+
+		/// Pause the call `call_name` of `pallet_name`.
+		#[pallet::weight(T::WeightInfo::pause_call())]
+		pub fn pause_call(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			call_name: Vec<u8>,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			let pallet = Self::pausable_pallet(&pallet_name)?;
+			ensure!(
+				<T as Config>::Call::get_call_names(pallet)
+					.iter()
+					.any(|name| name.as_bytes() == &call_name[..]),
+				Error::<T>::UnknownCall
+			);
+			let key = (pallet_name, call_name);
+			ensure!(!<PausedCalls<T>>::contains_key(&key), Error::<T>::AlreadyPaused);
+
+			<PausedCalls<T>>::insert(&key, ());
+			Self::deposit_event(Event::CallPaused(key.0, key.1));
+			Ok(())
+		}
+
+		/// Resume the call `call_name` of `pallet_name` paused with `pause_call`. It stays paused
+		/// while its whole pallet is.
+		#[pallet::weight(T::WeightInfo::resume_call())]
+		pub fn resume_call(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			call_name: Vec<u8>,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			let key = (pallet_name, call_name);
+			ensure!(<PausedCalls<T>>::take(&key).is_some(), Error::<T>::NotPaused);
+
+			Self::deposit_event(Event::CallResumed(key.0, key.1));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether the call `call_name` of `pallet_name` is paused, on its own or with its pallet.
+		pub fn is_paused(pallet_name: &[u8], call_name: &[u8]) -> bool {
+			if Self::is_unpausable(pallet_name) {
+				return false
+			}
+			<PausedPallets<T>>::contains_key(pallet_name) ||
+				<PausedCalls<T>>::contains_key((pallet_name, call_name))
+		}
+
+		pub(crate) fn is_unpausable(pallet_name: &[u8]) -> bool {
+			pallet_name == <Self as PalletInfoAccess>::name().as_bytes() ||
+				T::UnpausablePallets::contains(&pallet_name.to_vec())
+		}
+
+		/// The name of the runtime's pallet called `pallet_name`, if it has calls and may be
+		/// paused.
+		fn pausable_pallet(pallet_name: &[u8]) -> Result<&'static str, Error<T>> {
+			ensure!(!Self::is_unpausable(pallet_name), Error::<T>::CannotPause);
+			<T as Config>::Call::get_module_names()
+				.iter()
+				.find(|name| name.as_bytes() == pallet_name)
+				.copied()
+				.ok_or(Error::<T>::UnknownPallet)
+		}
+	}
+
+	/// Contains the calls that are not paused. Use it as the runtime's `BaseCallFilter`, on its
+	/// own or combined with other filters.
+	pub struct NonPausedCalls<T>(PhantomData<T>);
+
+	impl<T: Config> Contains<<T as frame_system::Config>::Call> for NonPausedCalls<T> {
+		fn contains(call: &<T as frame_system::Config>::Call) -> bool {
+			let CallMetadata { pallet_name, function_name } =
+				<T as Config>::Call::from_ref(call).get_call_metadata();
+			!Pallet::<T>::is_paused(pallet_name.as_bytes(), function_name.as_bytes())
+		}
+	}
+}
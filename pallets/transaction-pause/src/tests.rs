@@ -0,0 +1,155 @@
+use crate::{mock::*, Error};
+use frame_support::{assert_noop, assert_ok, traits::Contains};
+use sp_runtime::{
+	traits::{BadOrigin, Dispatchable},
+	DispatchError,
+};
+
+type NonPausedCalls = crate::NonPausedCalls<Test>;
+type PauseEvent = crate::Event<Test>;
+
+fn filtered() -> DispatchError {
+	frame_system::Error::<Test>::CallFiltered.into()
+}
+
+#[test]
+fn pausing_a_pallet_filters_all_its_calls() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TransactionPause::pause_pallet(Origin::root(), b"Balances".to_vec()));
+		System::assert_last_event(PauseEvent::PalletPaused(b"Balances".to_vec()).into());
+
+		assert!(!NonPausedCalls::contains(&transfer(10)));
+		assert_eq!(transfer(10).dispatch(Origin::signed(1)).unwrap_err().error, filtered());
+		assert!(NonPausedCalls::contains(&remark()));
+
+		assert_ok!(TransactionPause::resume_pallet(Origin::root(), b"Balances".to_vec()));
+		System::assert_last_event(PauseEvent::PalletResumed(b"Balances".to_vec()).into());
+		assert_ok!(transfer(10).dispatch(Origin::signed(1)));
+		assert_eq!(Balances::free_balance(2), 110);
+	});
+}
+
+#[test]
+fn pausing_a_call_filters_only_that_call() {
+	new_test_ext().execute_with(|| {
+		let (pallet, call) = (b"Balances".to_vec(), b"transfer".to_vec());
+		assert_ok!(TransactionPause::pause_call(Origin::root(), pallet.clone(), call.clone()));
+		System::assert_last_event(PauseEvent::CallPaused(pallet.clone(), call.clone()).into());
+
+		assert_eq!(transfer(10).dispatch(Origin::signed(1)).unwrap_err().error, filtered());
+		assert!(NonPausedCalls::contains(&Call::Balances(
+			pallet_balances::Call::transfer_keep_alive { dest: 2, value: 10 }
+		)));
+
+		assert_ok!(TransactionPause::resume_call(Origin::root(), pallet.clone(), call.clone()));
+		System::assert_last_event(PauseEvent::CallResumed(pallet, call).into());
+		assert!(NonPausedCalls::contains(&transfer(10)));
+	});
+}
+
+#[test]
+fn a_call_stays_paused_while_its_pallet_is() {
+	new_test_ext().execute_with(|| {
+		let (pallet, call) = (b"Balances".to_vec(), b"transfer".to_vec());
+		assert_ok!(TransactionPause::pause_pallet(Origin::root(), pallet.clone()));
+		assert_ok!(TransactionPause::pause_call(Origin::root(), pallet.clone(), call.clone()));
+
+		assert_ok!(TransactionPause::resume_call(Origin::root(), pallet.clone(), call.clone()));
+		assert!(!NonPausedCalls::contains(&transfer(10)));
+
+		assert_ok!(TransactionPause::pause_call(Origin::root(), pallet.clone(), call));
+		assert_ok!(TransactionPause::resume_pallet(Origin::root(), pallet));
+		assert!(!NonPausedCalls::contains(&transfer(10)));
+	});
+}
+
+#[test]
+fn root_can_dispatch_paused_calls() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TransactionPause::pause_pallet(Origin::root(), b"Balances".to_vec()));
+
+		let set_balance = Call::Balances(pallet_balances::Call::set_balance {
+			who: 3,
+			new_free: 50,
+			new_reserved: 0,
+		});
+		assert_ok!(set_balance.dispatch(Origin::root()));
+		assert_eq!(Balances::free_balance(3), 50);
+	});
+}
+
+#[test]
+fn unpausable_pallets_cannot_be_paused() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TransactionPause::pause_pallet(Origin::root(), b"System".to_vec()),
+			Error::<Test>::CannotPause
+		);
+		assert_noop!(
+			TransactionPause::pause_call(Origin::root(), b"System".to_vec(), b"remark".to_vec()),
+			Error::<Test>::CannotPause
+		);
+		// The pallet's own calls stay available, so that paused calls can be resumed.
+		assert_noop!(
+			TransactionPause::pause_pallet(Origin::root(), b"TransactionPause".to_vec()),
+			Error::<Test>::CannotPause
+		);
+	});
+}
+
+#[test]
+fn unknown_names_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TransactionPause::pause_pallet(Origin::root(), b"Balance".to_vec()),
+			Error::<Test>::UnknownPallet
+		);
+		assert_noop!(
+			TransactionPause::pause_call(Origin::root(), b"Balances".to_vec(), b"send".to_vec()),
+			Error::<Test>::UnknownCall
+		);
+	});
+}
+
+#[test]
+fn pausing_and_resuming_twice_fails() {
+	new_test_ext().execute_with(|| {
+		let (pallet, call) = (b"Balances".to_vec(), b"transfer".to_vec());
+		assert_noop!(
+			TransactionPause::resume_pallet(Origin::root(), pallet.clone()),
+			Error::<Test>::NotPaused
+		);
+		assert_noop!(
+			TransactionPause::resume_call(Origin::root(), pallet.clone(), call.clone()),
+			Error::<Test>::NotPaused
+		);
+
+		assert_ok!(TransactionPause::pause_pallet(Origin::root(), pallet.clone()));
+		assert_ok!(TransactionPause::pause_call(Origin::root(), pallet.clone(), call.clone()));
+		assert_noop!(
+			TransactionPause::pause_pallet(Origin::root(), pallet.clone()),
+			Error::<Test>::AlreadyPaused
+		);
+		assert_noop!(
+			TransactionPause::pause_call(Origin::root(), pallet, call),
+			Error::<Test>::AlreadyPaused
+		);
+	});
+}
+
+#[test]
+fn only_the_pause_origin_can_pause_and_resume() {
+	new_test_ext().execute_with(|| {
+		let (pallet, call) = (b"Balances".to_vec(), b"transfer".to_vec());
+		assert_noop!(TransactionPause::pause_pallet(Origin::signed(1), pallet.clone()), BadOrigin);
+		assert_noop!(
+			TransactionPause::pause_call(Origin::signed(1), pallet.clone(), call.clone()),
+			BadOrigin
+		);
+
+		assert_ok!(TransactionPause::pause_pallet(Origin::root(), pallet.clone()));
+		assert_ok!(TransactionPause::pause_call(Origin::root(), pallet.clone(), call.clone()));
+		assert_noop!(TransactionPause::resume_pallet(Origin::signed(1), pallet.clone()), BadOrigin);
+		assert_noop!(TransactionPause::resume_call(Origin::signed(1), pallet, call), BadOrigin);
+	});
+}
@@ -0,0 +1,77 @@
+//! Weights for pallet_transaction_pause.
+//!
+//! Rounded upper bounds until `scripts/benchmark.sh` is run, which overwrites this file. Pausing
+//! searches the runtime's pallet names, and for a single call its call names as well, which resuming
+//! doesn't, so pausing is weighed higher.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_transaction_pause.
+pub trait WeightInfo {
+	fn pause_pallet() -> Weight;
+	fn resume_pallet() -> Weight;
+	fn pause_call() -> Weight;
+	fn resume_call() -> Weight;
+}
+
+/// Weights for pallet_transaction_pause using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: TransactionPause PausedPallets (r:1 w:1)
+	fn pause_pallet() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TransactionPause PausedPallets (r:1 w:1)
+	fn resume_pallet() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TransactionPause PausedCalls (r:1 w:1)
+	fn pause_call() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TransactionPause PausedCalls (r:1 w:1)
+	fn resume_call() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: TransactionPause PausedPallets (r:1 w:1)
+	fn pause_pallet() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TransactionPause PausedPallets (r:1 w:1)
+	fn resume_pallet() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TransactionPause PausedCalls (r:1 w:1)
+	fn pause_call() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TransactionPause PausedCalls (r:1 w:1)
+	fn resume_call() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
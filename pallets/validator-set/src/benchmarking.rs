@@ -0,0 +1,52 @@
+//! Benchmarking setup for pallet-validator-set
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as ValidatorSet;
+use codec::Decode;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::{EnsureOrigin, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::TrailingZeroInput;
+use sp_std::vec::Vec;
+
+/// Fill the validator set with `count` accounts.
+fn fill<T: Config>(count: u32) -> Vec<T::AccountId> {
+	let validators: Vec<T::AccountId> = (0..count).map(|i| account("validator", i, 0)).collect();
+	Validators::<T>::put(&validators);
+	validators
+}
+
+/// An account with session keys, which nobody else in the set uses.
+fn with_keys<T: Config>(who: T::AccountId) -> Result<T::AccountId, &'static str> {
+	let keys = T::Keys::decode(&mut TrailingZeroInput::new(&[][..]))
+		.map_err(|_| "session keys can't be decoded from zeroes")?;
+	frame_system::Pallet::<T>::inc_providers(&who);
+	pallet_session::Pallet::<T>::set_keys(RawOrigin::Signed(who.clone()).into(), keys, Vec::new())?;
+	Ok(who)
+}
+
+benchmarks! {
+	add_validator {
+		let v in 0 .. T::MaxValidators::get() - 1;
+		fill::<T>(v);
+		let validator = with_keys::<T>(account("new", 0, 0))?;
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin, validator.clone())
+	verify {
+		assert!(Pallet::<T>::validators().contains(&validator));
+	}
+
+	remove_validator {
+		let v in T::MinValidators::get() + 1 .. T::MaxValidators::get();
+		// The last validator is the slowest to find.
+		let validator = fill::<T>(v).pop().expect("at least one validator; qed");
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin, validator.clone())
+	verify {
+		assert!(!Pallet::<T>::validators().contains(&validator));
+	}
+
+	impl_benchmark_test_suite!(ValidatorSet, crate::mock::new_test_ext(), crate::mock::Test);
+}
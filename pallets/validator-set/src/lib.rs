@@ -0,0 +1,303 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Manages the validator set of a proof-of-authority chain.
+///
+/// The set starts out as configured at genesis and can be changed by a privileged origin.
+/// `pallet_session` asks for it at every session boundary, and the session handlers pass the
+/// validators' session keys on to Aura and GRANDPA. Validators reported for an offence are
+/// disabled for the rest of the session and dropped from the set.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{SortedMembers, ValidatorRegistration},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{traits::Convert, Perbill};
+	use sp_staking::{
+		offence::{DisableStrategy, OffenceDetails, OnOffenceHandler},
+		SessionIndex,
+	};
+	use sp_std::{marker::PhantomData, prelude::*};
+
+	pub use crate::weights::WeightInfo;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config
+		+ pallet_session::Config<ValidatorId = <Self as frame_system::Config>::AccountId>
+	{
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Origin allowed to add and remove validators.
+		type AddRemoveOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The smallest number of validators the set may shrink to.
+		#[pallet::constant]
+		type MinValidators: Get<u32>;
+
+		/// The largest number of validators the set may grow to.
+		#[pallet::constant]
+		type MaxValidators: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// The accounts that make up the validator set of the next session.
+	#[pallet::storage]
+	#[pallet::getter(fn validators)]
+	pub type Validators<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A validator joins the set from the next session on. [validator]
+		ValidatorAdded(T::AccountId),
+		/// A validator leaves the set from the next session on. [validator]
+		ValidatorRemoved(T::AccountId),
+		/// A validator was reported for an offence and disabled for the rest of the session. It is
+		/// also removed from the set, which is reported with `ValidatorRemoved`. [validator]
+		ValidatorDisabled(T::AccountId),
+		/// A validator was reported for an offence but stays in the set, because removing it would
+		/// leave fewer than `MinValidators`. [validator]
+		ValidatorKept(T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is already a validator.
+		AlreadyValidator,
+		/// The account is not a validator.
+		NotValidator,
+		/// The account has not registered session keys.
+		NoSessionKeys,
+		/// Removing the validator would leave fewer than `MinValidators`.
+		TooFewValidators,
+		/// Adding the validator would exceed `MaxValidators`.
+		TooManyValidators,
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub validators: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { validators: Vec::new() }
+		}
+	}
+
+	/// The session keys are set after this, by `pallet_session`'s genesis, so they are checked
+	/// when it asks for the first session's validators.
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			assert!(!self.validators.is_empty(), "The genesis validator set is empty");
+			for (index, validator) in self.validators.iter().enumerate() {
+				assert!(
+					!self.validators[..index].contains(validator),
+					"Genesis validator {:?} is listed twice",
+					validator
+				);
+			}
+			<Validators<T>>::put(&self.validators);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add `validator` to the set. It takes part in consensus two sessions later, once its
+		/// session keys have been queued.
+		///
+		/// The account must have registered session keys with `Session::set_keys`. The weight of
+		/// validators not in the set is refunded.
+		#[pallet::weight(T::WeightInfo::add_validator(T::MaxValidators::get()))]
+		pub fn add_validator(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+			ensure!(
+				<pallet_session::Pallet<T> as ValidatorRegistration<_>>::is_registered(&validator),
+				Error::<T>::NoSessionKeys
+			);
+
+			let count = <Validators<T>>::try_mutate(|validators| -> Result<u32, DispatchError> {
+				ensure!(!validators.contains(&validator), Error::<T>::AlreadyValidator);
+				let count = validators.len() as u32;
+				ensure!(count < T::MaxValidators::get(), Error::<T>::TooManyValidators);
+				validators.push(validator.clone());
+				Ok(count)
+			})?;
+
+			Self::deposit_event(Event::ValidatorAdded(validator));
+			Ok(Some(T::WeightInfo::add_validator(count)).into())
+		}
+
+		/// Remove `validator` from the set. It stops taking part in consensus two sessions later.
+		/// The weight of validators not in the set is refunded.
+		#[pallet::weight(T::WeightInfo::remove_validator(T::MaxValidators::get()))]
+		pub fn remove_validator(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			let count = <Validators<T>>::try_mutate(|validators| -> Result<u32, DispatchError> {
+				let index = validators
+					.iter()
+					.position(|v| v == &validator)
+					.ok_or(Error::<T>::NotValidator)?;
+				ensure!(
+					validators.len() as u32 > T::MinValidators::get(),
+					Error::<T>::TooFewValidators
+				);
+				let count = validators.len() as u32;
+				validators.remove(index);
+				Ok(count)
+			})?;
+
+			Self::deposit_event(Event::ValidatorRemoved(validator));
+			Ok(Some(T::WeightInfo::remove_validator(count)).into())
+		}
+	}
+
+	/// The validator set, for other pallets to grant validators rights. A validator counts as
+	/// soon as it is added, before its first session.
+	impl<T: Config> SortedMembers<T::AccountId> for Pallet<T> {
+		fn sorted_members() -> Vec<T::AccountId> {
+			let mut validators = Self::validators();
+			validators.sort();
+			validators
+		}
+
+		fn contains(who: &T::AccountId) -> bool {
+			Self::validators().contains(who)
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn add(who: &T::AccountId) {
+			<Validators<T>>::append(who)
+		}
+	}
+
+	impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
+		fn new_session(_new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+			Some(Self::validators())
+		}
+
+		fn new_session_genesis(_new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+			let validators = Self::validators();
+			for validator in &validators {
+				assert!(
+					<pallet_session::Pallet<T> as ValidatorRegistration<_>>::is_registered(
+						validator
+					),
+					"Genesis validator {:?} has no session keys",
+					validator
+				);
+			}
+			Some(validators)
+		}
+
+		fn end_session(_end_index: SessionIndex) {}
+
+		fn start_session(_start_index: SessionIndex) {}
+	}
+
+	/// A validator is fully identified by its account, so the historical session data records
+	/// each validator's account as its full identification.
+	impl<T: Config> pallet_session::historical::SessionManager<T::AccountId, T::AccountId>
+		for Pallet<T>
+	{
+		fn new_session(new_index: SessionIndex) -> Option<Vec<(T::AccountId, T::AccountId)>> {
+			<Self as pallet_session::SessionManager<_>>::new_session(new_index)
+				.map(|validators| validators.into_iter().map(|v| (v.clone(), v)).collect())
+		}
+
+		fn new_session_genesis(
+			new_index: SessionIndex,
+		) -> Option<Vec<(T::AccountId, T::AccountId)>> {
+			<Self as pallet_session::SessionManager<_>>::new_session_genesis(new_index)
+				.map(|validators| validators.into_iter().map(|v| (v.clone(), v)).collect())
+		}
+
+		fn end_session(end_index: SessionIndex) {
+			<Self as pallet_session::SessionManager<_>>::end_session(end_index)
+		}
+
+		fn start_session(start_index: SessionIndex) {
+			<Self as pallet_session::SessionManager<_>>::start_session(start_index)
+		}
+	}
+
+	/// Disables reported validators in `pallet_session` straight away, so Aura stops accepting
+	/// their blocks, and drops them from the set of the following sessions. A validator is kept
+	/// in the set if removing it would leave fewer than `MinValidators`.
+	impl<T: Config, FullIdentification>
+		OnOffenceHandler<T::AccountId, (T::AccountId, FullIdentification), Weight> for Pallet<T>
+	{
+		fn on_offence(
+			offenders: &[OffenceDetails<T::AccountId, (T::AccountId, FullIdentification)>],
+			_slash_fraction: &[Perbill],
+			_session: SessionIndex,
+			_disable_strategy: DisableStrategy,
+		) -> Weight {
+			let mut validators = Self::validators();
+
+			for details in offenders {
+				let offender = &details.offender.0;
+				// Offenders that are not in the current session can't be disabled.
+				if pallet_session::Pallet::<T>::disable(offender) {
+					Self::deposit_event(Event::ValidatorDisabled(offender.clone()));
+				}
+
+				if let Some(index) = validators.iter().position(|v| v == offender) {
+					if validators.len() as u32 > T::MinValidators::get() {
+						validators.remove(index);
+						Self::deposit_event(Event::ValidatorRemoved(offender.clone()));
+					} else {
+						Self::deposit_event(Event::ValidatorKept(offender.clone()));
+					}
+				}
+			}
+
+			<Validators<T>>::put(validators);
+
+			let count = offenders.len() as Weight;
+			T::DbWeight::get().reads_writes(1 + 2 * count, 1 + 2 * count)
+		}
+	}
+
+	/// Maps an account to itself, for use as both `ValidatorIdOf` in `pallet_session` and
+	/// `FullIdentificationOf` in `pallet_session::historical`.
+	pub struct ValidatorOf<T>(PhantomData<T>);
+
+	impl<T: Config> Convert<T::AccountId, Option<T::AccountId>> for ValidatorOf<T> {
+		fn convert(account: T::AccountId) -> Option<T::AccountId> {
+			Some(account)
+		}
+	}
+}
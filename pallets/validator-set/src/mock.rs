@@ -0,0 +1,131 @@
+use crate as pallet_validator_set;
+use frame_support::{parameter_types, traits::GenesisBuild};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::{Header, UintAuthorityId},
+	traits::{BlakeTwo256, IdentityLookup, OpaqueKeys},
+	KeyTypeId, RuntimeAppPublic,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		ValidatorSet: pallet_validator_set::{Pallet, Call, Storage, Event<T>, Config<T>},
+		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+}
+
+/// Session handler that accepts any keys and ignores session changes.
+pub struct TestSessionHandler;
+
+impl pallet_session::SessionHandler<u64> for TestSessionHandler {
+	const KEY_TYPE_IDS: &'static [KeyTypeId] = &[UintAuthorityId::ID];
+
+	fn on_genesis_session<Ks: OpaqueKeys>(_validators: &[(u64, Ks)]) {}
+
+	fn on_new_session<Ks: OpaqueKeys>(
+		_changed: bool,
+		_validators: &[(u64, Ks)],
+		_queued_validators: &[(u64, Ks)],
+	) {
+	}
+
+	fn on_disabled(_validator_index: u32) {}
+}
+
+parameter_types! {
+	pub const Period: u64 = 1;
+	pub const Offset: u64 = 0;
+}
+
+impl pallet_session::Config for Test {
+	type Event = Event;
+	type ValidatorId = u64;
+	type ValidatorIdOf = pallet_validator_set::ValidatorOf<Self>;
+	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
+	type SessionManager = ValidatorSet;
+	type SessionHandler = TestSessionHandler;
+	type Keys = UintAuthorityId;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinValidators: u32 = 2;
+	pub const MaxValidators: u32 = 4;
+}
+
+impl pallet_validator_set::Config for Test {
+	type Event = Event;
+	type AddRemoveOrigin = frame_system::EnsureRoot<u64>;
+	type MinValidators = MinValidators;
+	type MaxValidators = MaxValidators;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime, with accounts 1, 2 and 3 as validators.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	build_genesis(&[1, 2, 3], &[1, 2, 3])
+}
+
+/// Build genesis storage with `validators` as the validator set, and session keys for `keyed`.
+pub fn build_genesis(validators: &[u64], keyed: &[u64]) -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	pallet_validator_set::GenesisConfig::<Test> { validators: validators.to_vec() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	pallet_session::GenesisConfig::<Test> {
+		keys: keyed.iter().map(|&v| (v, v, UintAuthorityId(v))).collect(),
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	t.into()
+}
+
+/// Give `who` session keys, as `Session::set_keys` would from a signed extrinsic.
+pub fn register_keys(who: u64) {
+	System::inc_providers(&who);
+	Session::set_keys(Origin::signed(who), UintAuthorityId(who), vec![]).unwrap();
+}
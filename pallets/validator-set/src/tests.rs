@@ -0,0 +1,196 @@
+use crate::{mock::*, weights::WeightInfo, Error};
+use frame_support::{assert_noop, assert_ok, traits::SortedMembers, weights::Weight};
+use sp_runtime::{traits::BadOrigin, Perbill};
+use sp_staking::offence::{DisableStrategy, OffenceDetails, OnOffenceHandler};
+
+#[test]
+fn genesis_validators_form_the_first_session() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ValidatorSet::validators(), vec![1, 2, 3]);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+	});
+}
+
+#[test]
+#[should_panic(expected = "The genesis validator set is empty")]
+fn genesis_validators_must_not_be_empty() {
+	build_genesis(&[], &[1, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "Genesis validator 2 is listed twice")]
+fn genesis_validators_must_be_distinct() {
+	build_genesis(&[1, 2, 2], &[1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "Genesis validator 3 has no session keys")]
+fn genesis_validators_must_have_session_keys() {
+	build_genesis(&[1, 2, 3], &[1, 2]);
+}
+
+#[test]
+fn add_validator_takes_effect_two_sessions_later() {
+	new_test_ext().execute_with(|| {
+		register_keys(4);
+
+		assert!(!ValidatorSet::contains(&4));
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_eq!(ValidatorSet::validators(), vec![1, 2, 3, 4]);
+		assert!(ValidatorSet::contains(&4));
+
+		Session::rotate_session();
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+		Session::rotate_session();
+		assert_eq!(Session::validators(), vec![1, 2, 3, 4]);
+	});
+}
+
+#[test]
+fn add_validator_checks_origin_keys_and_bounds() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ValidatorSet::add_validator(Origin::signed(1), 4), BadOrigin);
+		assert_noop!(ValidatorSet::add_validator(Origin::root(), 4), Error::<Test>::NoSessionKeys);
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 1),
+			Error::<Test>::AlreadyValidator
+		);
+
+		register_keys(4);
+		register_keys(5);
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 5),
+			Error::<Test>::TooManyValidators
+		);
+	});
+}
+
+#[test]
+fn weight_follows_the_size_of_the_set() {
+	new_test_ext().execute_with(|| {
+		register_keys(4);
+
+		let added = ValidatorSet::add_validator(Origin::root(), 4).unwrap();
+		assert_eq!(added.actual_weight, Some(<() as WeightInfo>::add_validator(3)));
+		let removed = ValidatorSet::remove_validator(Origin::root(), 4).unwrap();
+		assert_eq!(removed.actual_weight, Some(<() as WeightInfo>::remove_validator(4)));
+	});
+}
+
+#[test]
+fn remove_validator_takes_effect_two_sessions_later() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 2));
+		assert_eq!(ValidatorSet::validators(), vec![1, 3]);
+
+		Session::rotate_session();
+		Session::rotate_session();
+		assert_eq!(Session::validators(), vec![1, 3]);
+	});
+}
+
+#[test]
+fn remove_validator_checks_origin_membership_and_bounds() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ValidatorSet::remove_validator(Origin::signed(1), 2), BadOrigin);
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 9),
+			Error::<Test>::NotValidator
+		);
+
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 2));
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 3),
+			Error::<Test>::TooFewValidators
+		);
+	});
+}
+
+type SetEvent = crate::Event<Test>;
+
+/// Report `offenders` for an offence in the current session.
+fn report(offenders: &[u64]) {
+	let offences: Vec<_> = offenders
+		.iter()
+		.map(|&o| OffenceDetails { offender: (o, o), reporters: vec![] })
+		.collect();
+	<ValidatorSet as OnOffenceHandler<u64, (u64, u64), Weight>>::on_offence(
+		&offences,
+		&vec![Perbill::zero(); offences.len()],
+		0,
+		DisableStrategy::Always,
+	);
+}
+
+/// The events of the validator set pallet deposited so far.
+fn set_events() -> Vec<SetEvent> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			Event::ValidatorSet(event) => Some(event),
+			_ => None,
+		})
+		.collect()
+}
+
+#[test]
+fn offenders_are_disabled_and_removed() {
+	new_test_ext().execute_with(|| {
+		let offence = OffenceDetails { offender: (2, 2), reporters: vec![] };
+
+		<ValidatorSet as OnOffenceHandler<u64, (u64, u64), Weight>>::on_offence(
+			&[offence],
+			&[Perbill::zero()],
+			0,
+			DisableStrategy::Always,
+		);
+
+		assert_eq!(Session::disabled_validators(), vec![1]);
+		assert_eq!(ValidatorSet::validators(), vec![1, 3]);
+	});
+}
+
+#[test]
+fn offence_events_report_what_happened() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 3));
+
+		// 3 still validates in the current session, but is no longer in the set.
+		report(&[3]);
+		assert_eq!(
+			set_events(),
+			vec![SetEvent::ValidatorRemoved(3), SetEvent::ValidatorDisabled(3)]
+		);
+
+		// 2 is disabled, but the set can't shrink any further.
+		System::reset_events();
+		report(&[2]);
+		assert_eq!(set_events(), vec![SetEvent::ValidatorDisabled(2), SetEvent::ValidatorKept(2)]);
+		assert_eq!(ValidatorSet::validators(), vec![1, 2]);
+
+		// Accounts outside the current session and the set are neither disabled nor kept.
+		System::reset_events();
+		report(&[4]);
+		assert!(set_events().is_empty());
+	});
+}
+
+#[test]
+fn offenders_stay_in_a_minimal_set() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 3));
+		let offence = OffenceDetails { offender: (2, 2), reporters: vec![] };
+
+		<ValidatorSet as OnOffenceHandler<u64, (u64, u64), Weight>>::on_offence(
+			&[offence],
+			&[Perbill::zero()],
+			0,
+			DisableStrategy::Always,
+		);
+
+		assert_eq!(Session::disabled_validators(), vec![1]);
+		assert_eq!(ValidatorSet::validators(), vec![1, 2]);
+	});
+}
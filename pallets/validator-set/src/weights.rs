@@ -0,0 +1,57 @@
+//! Weights for pallet_validator_set.
+//!
+//! Rounded upper bounds until `scripts/benchmark.sh` is run, which overwrites this file. Both calls
+//! scan the validator list, so they are weighed by its length `v` and refund what a set smaller than
+//! `MaxValidators` doesn't use.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_validator_set.
+pub trait WeightInfo {
+	fn add_validator(v: u32, ) -> Weight;
+	fn remove_validator(v: u32, ) -> Weight;
+}
+
+/// Weights for pallet_validator_set using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Session NextKeys (r:1 w:0)
+	// Storage: ValidatorSet Validators (r:1 w:1)
+	fn add_validator(v: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((200_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ValidatorSet Validators (r:1 w:1)
+	fn remove_validator(v: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((200_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Session NextKeys (r:1 w:0)
+	// Storage: ValidatorSet Validators (r:1 w:1)
+	fn add_validator(v: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((200_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ValidatorSet Validators (r:1 w:1)
+	fn remove_validator(v: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((200_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
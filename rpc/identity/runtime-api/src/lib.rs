@@ -0,0 +1,31 @@
+//! Runtime API definition for resolving accounts to the names of their identities.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// The name an account goes by, from the identity pallet.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct DisplayName {
+	/// The display name of the account's identity, as UTF-8. A sub-account's name is its parent's
+	/// display name and its own name joined with a `/`.
+	pub name: Vec<u8>,
+	/// Whether a registrar judged the identity to be `KnownGood` or `Reasonable`. For a
+	/// sub-account this is the judgement of its parent's identity.
+	pub verified: bool,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Names of accounts, so that clients can show people rather than addresses.
+	pub trait IdentityApi<AccountId>
+	where
+		AccountId: Codec,
+	{
+		/// The name `who` goes by, or `None` if neither it nor its parent account set a display
+		/// name.
+		fn display_name(who: AccountId) -> Option<DisplayName>;
+	}
+}
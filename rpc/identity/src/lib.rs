@@ -0,0 +1,92 @@
+//! RPC interface for resolving accounts to the names of their identities.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use identity_rpc_runtime_api::{DisplayName, IdentityApi as IdentityRuntimeApi};
+
+/// The name an account goes by, as returned by `identity_displayName`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountName {
+	/// The display name, with a sub-account's parent name and own name joined by a `/`. Invalid
+	/// UTF-8 is replaced with U+FFFD.
+	pub display: String,
+	/// Whether a registrar judged the identity to be `KnownGood` or `Reasonable`.
+	pub verified: bool,
+}
+
+impl From<DisplayName> for AccountName {
+	fn from(name: DisplayName) -> Self {
+		Self { display: String::from_utf8_lossy(&name.name).into_owned(), verified: name.verified }
+	}
+}
+
+/// RPC methods resolving accounts to their identities.
+#[rpc]
+pub trait IdentityNameApi<BlockHash, AccountId> {
+	/// Returns the display name of `who`, or of its parent if `who` is a sub-account, at the given
+	/// block or the best one. `null` if there is none.
+	#[rpc(name = "identity_displayName")]
+	fn display_name(&self, who: AccountId, at: Option<BlockHash>) -> Result<Option<AccountName>>;
+}
+
+/// Implements [`IdentityNameApi`] by calling into the runtime.
+pub struct IdentityName<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> IdentityName<C, Block> {
+	/// Create a new `IdentityName` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId> IdentityNameApi<<Block as BlockT>::Hash, AccountId>
+	for IdentityName<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: IdentityRuntimeApi<Block, AccountId>,
+	AccountId: Codec,
+{
+	fn display_name(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<AccountName>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let name = self.client.runtime_api().display_name(&at, who).map_err(|e| RpcError {
+			code: ErrorCode::InternalError,
+			message: "Unable to query the display name.".into(),
+			data: Some(e.to_string().into()),
+		})?;
+		Ok(name.map(Into::into))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn display_names_are_converted_lossily() {
+		let name = DisplayName { name: b"Alice/ops".to_vec(), verified: true };
+		assert_eq!(
+			AccountName::from(name),
+			AccountName { display: "Alice/ops".into(), verified: true }
+		);
+
+		let name = DisplayName { name: vec![b'A', 0xff], verified: false };
+		assert_eq!(AccountName::from(name).display, "A\u{fffd}");
+	}
+}
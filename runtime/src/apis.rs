@@ -0,0 +1,40 @@
+//! Runtime APIs declared by this runtime, rather than by one of its pallets.
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// How the chain's token and addresses are presented to users.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ChainProperties {
+	/// The ticker of the native token, as UTF-8.
+	pub token_symbol: Vec<u8>,
+	/// The number of decimal places of the native token.
+	pub token_decimals: u8,
+	/// The SS58 prefix of the chain's addresses.
+	pub ss58_format: u16,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The token and address properties of the chain, so that chain specs, nodes and clients can
+	/// check they agree with the runtime.
+	pub trait ChainPropertiesApi {
+		/// The chain's token and address properties.
+		fn properties() -> ChainProperties;
+	}
+
+	/// Balance queries for the assets of the assets pallet.
+	pub trait AssetsApi<AccountId, AssetId, AssetBalance>
+	where
+		AccountId: Codec,
+		AssetId: Codec,
+		AssetBalance: Codec,
+	{
+		/// The balance of `who` in `asset`. Zero if the asset does not exist.
+		fn balance(asset: AssetId, who: AccountId) -> AssetBalance;
+
+		/// The total supply of `asset`, or `None` if it does not exist.
+		fn total_supply(asset: AssetId) -> Option<AssetBalance>;
+	}
+}
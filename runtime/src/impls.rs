@@ -0,0 +1,639 @@
+//! Some configurable implementations as associated type for the runtime.
+
+use crate::{
+	AccountId, Assets, Aura, Balance, Balances, Call, Identity, Runtime, Session, Treasury,
+	MAXIMUM_BLOCK_WEIGHT, NORMAL_DISPATCH_RATIO, UNIT,
+};
+use frame_support::{
+	traits::{
+		fungibles::{Balanced, CreditOf},
+		Contains, Currency, ExistenceRequirement, FindAuthor, Imbalance, OnUnbalanced,
+	},
+	weights::{
+		constants::WEIGHT_PER_SECOND, Weight, WeightToFeeCoefficient, WeightToFeeCoefficients,
+		WeightToFeePolynomial,
+	},
+};
+use identity_rpc_runtime_api::DisplayName;
+use pallet_asset_tx_payment::HandleCredit;
+use pallet_evm::{EVMCurrencyAdapter, OnChargeEVMTransaction};
+use pallet_identity::{Data, Judgement};
+use smallvec::smallvec;
+use sp_core::{H160, U256};
+use sp_runtime::{traits::UniqueSaturatedInto, ConsensusEngineId, Perbill};
+use sp_std::vec::Vec;
+
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// Looks up the author of the current block from the Aura authorities and the current slot.
+pub struct AuraAuthor;
+
+impl AuraAuthor {
+	/// The account of the authority whose turn the current slot is.
+	///
+	/// Aura takes its authorities from the session keys of the validators, in the same order, so
+	/// the author's account is the session validator at the authority's index.
+	pub fn current() -> Option<AccountId> {
+		let authorities = Aura::authorities();
+		if authorities.is_empty() {
+			return None
+		}
+		let index = *Aura::current_slot() % authorities.len() as u64;
+		Session::validators().get(index as usize).cloned()
+	}
+}
+
+impl FindAuthor<AccountId> for AuraAuthor {
+	fn find_author<'a, I>(_digests: I) -> Option<AccountId>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		Self::current()
+	}
+}
+
+/// Pays the author of the current block. Goes to the treasury if the author is unknown.
+pub struct ToAuthor;
+
+impl OnUnbalanced<NegativeImbalance> for ToAuthor {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		match AuraAuthor::current() {
+			Some(author) => Balances::resolve_creating(&author, amount),
+			None => Treasury::on_unbalanced(amount),
+		}
+	}
+}
+
+/// The parts of transaction fees that go to the treasury and to the block author.
+const FEE_SPLIT: (u32, u32) = (80, 20);
+
+/// Splits transaction fees 80% to the treasury and 20% to the block author. Tips go to the block
+/// author in full.
+pub struct DealWithFees;
+
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
+		if let Some(fees) = fees_then_tips.next() {
+			let (to_treasury, mut to_author) = fees.ration(FEE_SPLIT.0, FEE_SPLIT.1);
+			if let Some(tips) = fees_then_tips.next() {
+				tips.merge_into(&mut to_author);
+			}
+			Treasury::on_unbalanced(to_treasury);
+			ToAuthor::on_unbalanced(to_author);
+		}
+	}
+
+	// EVM fees arrive on their own, with the priority fee included. `EvmFees` passes the treasury's
+	// share of the priority fee on to the author afterwards.
+	fn on_nonzero_unbalanced(fees: NegativeImbalance) {
+		let (to_treasury, to_author) = fees.ration(FEE_SPLIT.0, FEE_SPLIT.1);
+		Treasury::on_unbalanced(to_treasury);
+		ToAuthor::on_unbalanced(to_author);
+	}
+}
+
+/// Charges EVM gas fees like [`EVMCurrencyAdapter`], splitting them with [`DealWithFees`], and
+/// gives the block author the whole priority fee.
+///
+/// The fee the EVM corrects the charge to includes the priority fee, so [`DealWithFees`] splits it
+/// along with the gas fee, and only then is the priority fee reported on its own. The adapter would
+/// mint it a second time for the account mapped from the author's truncated address, which nobody
+/// holds the key of. Instead the treasury's share of it is moved on to the author.
+pub struct EvmFees;
+
+type EvmFeesAdapter = EVMCurrencyAdapter<Balances, DealWithFees>;
+
+impl OnChargeEVMTransaction<Runtime> for EvmFees {
+	type LiquidityInfo = <EvmFeesAdapter as OnChargeEVMTransaction<Runtime>>::LiquidityInfo;
+
+	fn withdraw_fee(
+		who: &H160,
+		fee: U256,
+	) -> Result<Self::LiquidityInfo, pallet_evm::Error<Runtime>> {
+		EvmFeesAdapter::withdraw_fee(who, fee)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &H160,
+		corrected_fee: U256,
+		already_withdrawn: Self::LiquidityInfo,
+	) {
+		EvmFeesAdapter::correct_and_deposit_fee(who, corrected_fee, already_withdrawn)
+	}
+
+	fn pay_priority_fee(tip: U256) {
+		// Without a known author, the author's share went to the treasury as well.
+		if let Some(author) = AuraAuthor::current() {
+			let tip: Balance = tip.unique_saturated_into();
+			let share = Perbill::from_rational(FEE_SPLIT.0, FEE_SPLIT.0 + FEE_SPLIT.1) * tip;
+			let paid = Balances::transfer(
+				&Treasury::account_id(),
+				&author,
+				share,
+				ExistenceRequirement::KeepAlive,
+			);
+			// Fails if it would leave the treasury below the existential deposit, in which case the
+			// treasury keeps the share.
+			if let Err(e) = paid {
+				log::warn!(
+					target: "runtime::evm",
+					"failed to pay a priority fee of {} to the block author {:?}: {:?}",
+					share,
+					author,
+					e,
+				);
+			}
+		}
+	}
+}
+
+/// Reports the author of the current block to the EVM, as the first 20 bytes of their account.
+///
+/// The address is only what the `COINBASE` opcode returns. EVM fees are paid to the author's
+/// account itself by [`EvmFees`].
+pub struct FindAuthorTruncated;
+
+impl FindAuthor<H160> for FindAuthorTruncated {
+	fn find_author<'a, I>(_digests: I) -> Option<H160>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		AuraAuthor::current().map(|author| H160::from_slice(&AsRef::<[u8]>::as_ref(&author)[..20]))
+	}
+}
+
+/// How much EVM gas a second of block execution time buys, as on the Ethereum mainnet.
+pub const GAS_PER_SECOND: u64 = 40_000_000;
+
+/// The weight of one unit of EVM gas.
+pub const WEIGHT_PER_GAS: Weight = WEIGHT_PER_SECOND / GAS_PER_SECOND;
+
+/// Converts between EVM gas and weight at [`WEIGHT_PER_GAS`].
+pub struct GasWeightMapping;
+
+impl pallet_evm::GasWeightMapping for GasWeightMapping {
+	fn gas_to_weight(gas: u64) -> Weight {
+		gas.saturating_mul(WEIGHT_PER_GAS)
+	}
+
+	fn weight_to_gas(weight: Weight) -> u64 {
+		weight / WEIGHT_PER_GAS
+	}
+}
+
+/// Whether `call`, or any call it wraps in a batch, a proxy or a multisig, is paused. The calls a
+/// wrapper dispatches are only filtered when the wrapper runs, so the transaction pool checks them
+/// up front. Calls dispatched as root, such as through sudo, bypass the filter and are not checked.
+pub fn is_paused(call: &Call) -> bool {
+	if !<Runtime as frame_system::Config>::BaseCallFilter::contains(call) {
+		return true
+	}
+	match call {
+		Call::Utility(pallet_utility::Call::batch { calls }) |
+		Call::Utility(pallet_utility::Call::batch_all { calls }) => calls.iter().any(is_paused),
+		Call::Utility(pallet_utility::Call::as_derivative { call, .. }) |
+		Call::Proxy(pallet_proxy::Call::proxy { call, .. }) |
+		Call::Proxy(pallet_proxy::Call::proxy_announced { call, .. }) |
+		Call::Multisig(pallet_multisig::Call::as_multi_threshold_1 { call, .. }) => is_paused(call),
+		// A call that doesn't decode fails on its own once the multisig executes it.
+		Call::Multisig(pallet_multisig::Call::as_multi { call, .. }) =>
+			call.try_decode().map_or(false, |call| is_paused(&call)),
+		_ => false,
+	}
+}
+
+/// The name `who` goes by: the display name of its identity, or its parent's display name and its
+/// own name joined with a `/` for a sub-account.
+pub fn display_name(who: &AccountId) -> Option<DisplayName> {
+	let (identity, sub_name) = match Identity::super_of(who) {
+		Some((parent, sub_name)) => (parent, raw_data(&sub_name)),
+		None => (who.clone(), None),
+	};
+	let registration = Identity::identity(&identity)?;
+
+	let mut name = raw_data(&registration.info.display)?;
+	if let Some(sub_name) = sub_name {
+		name.push(b'/');
+		name.extend(sub_name);
+	}
+	let verified = registration
+		.judgements
+		.iter()
+		.any(|(_, judgement)| matches!(judgement, Judgement::KnownGood | Judgement::Reasonable));
+	Some(DisplayName { name, verified })
+}
+
+/// The bytes of a raw identity field. Empty and hashed fields have none to show.
+fn raw_data(data: &Data) -> Option<Vec<u8>> {
+	match data {
+		Data::Raw(bytes) if !bytes.is_empty() => Some(bytes.to_vec()),
+		_ => None,
+	}
+}
+
+/// The fee of a block filled with normal transactions, before the fee multiplier is applied.
+pub const FULL_BLOCK_FEE: Balance = 10 * UNIT;
+
+/// Pays transaction fees charged in an asset to the author of the current block. They are burnt if
+/// the author is unknown or can't hold the asset.
+pub struct AssetFeesToAuthor;
+
+impl HandleCredit<AccountId, Assets> for AssetFeesToAuthor {
+	fn handle_credit(credit: CreditOf<AccountId, Assets>) {
+		if let Some(author) = AuraAuthor::current() {
+			// A credit that can't be resolved is dropped, which burns it.
+			let _ = Assets::resolve(&author, credit);
+		}
+	}
+}
+
+/// Converts weight to fee, calibrated so that a block filled with normal transactions costs
+/// [`FULL_BLOCK_FEE`] in total.
+///
+/// Half of that fee grows linearly with weight and half with its square, so a transaction costs
+/// more than two of half its weight. A transaction filling the block can't undercut the many
+/// smaller ones it crowds out.
+pub struct WeightToFee;
+
+impl WeightToFeePolynomial for WeightToFee {
+	type Balance = Balance;
+
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		// Each term makes up half of the fee of a full block of weight `q`.
+		let p = FULL_BLOCK_FEE / 2;
+		let q = Balance::from(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT);
+		let q_squared = q * q;
+		smallvec![
+			WeightToFeeCoefficient {
+				degree: 1,
+				negative: false,
+				coeff_frac: Perbill::from_rational(p % q, q),
+				coeff_integer: p / q,
+			},
+			WeightToFeeCoefficient {
+				degree: 2,
+				negative: false,
+				coeff_frac: Perbill::from_rational(p % q_squared, q_squared),
+				coeff_integer: p / q_squared,
+			},
+		]
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		opaque::SessionKeys, AdjustmentVariable, BlockGasLimit, BlockReward, BlockWeights,
+		ExistentialDeposit, MinimumMultiplier, Origin, System, TargetBlockFullness, EVM,
+	};
+	use frame_support::{
+		assert_ok,
+		storage::migration::put_storage_value,
+		traits::{GenesisBuild, OnInitialize},
+		weights::{constants::ExtrinsicBaseWeight, DispatchClass},
+	};
+	use pallet_evm::{AddressMapping, FeeCalculator, GasWeightMapping as _, HashedAddressMapping};
+	use pallet_identity::IdentityInfo;
+	use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+	use sp_consensus_aura::Slot;
+	use sp_core::{ed25519, sr25519};
+	use sp_runtime::{
+		assert_eq_error_rate,
+		traits::{BlakeTwo256, Convert, One},
+		FixedPointNumber,
+	};
+
+	type FeeUpdate =
+		TargetedFeeAdjustment<Runtime, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+
+	fn max_normal() -> Weight {
+		BlockWeights::get()
+			.get(DispatchClass::Normal)
+			.max_total
+			.unwrap_or_else(|| BlockWeights::get().max_block)
+	}
+
+	fn target() -> Weight {
+		TargetBlockFullness::get() * max_normal()
+	}
+
+	/// Run `f` with `weight` consumed in the current block.
+	fn with_block_weight<R>(weight: Weight, f: impl FnOnce() -> R) -> R {
+		let mut t: sp_io::TestExternalities = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into();
+		t.execute_with(|| {
+			System::set_block_consumed_resources(weight, 0);
+			f()
+		})
+	}
+
+	/// Apply the fee update `blocks` times to `multiplier`, for blocks carrying `weight`.
+	fn after_blocks(multiplier: Multiplier, weight: Weight, blocks: u32) -> Multiplier {
+		with_block_weight(weight, || (0..blocks).fold(multiplier, |m, _| FeeUpdate::convert(m)))
+	}
+
+	fn account(seed: u8) -> AccountId {
+		AccountId::new([seed; 32])
+	}
+
+	/// Externalities with a validator for each of `seeds`, in that order, at Aura slot `slot`.
+	fn with_validators(seeds: &[u8], slot: u64) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		// The session pallet refuses to start without validators.
+		if !seeds.is_empty() {
+			pallet_validator_set::GenesisConfig::<Runtime> {
+				validators: seeds.iter().map(|&seed| account(seed)).collect(),
+			}
+			.assimilate_storage(&mut t)
+			.unwrap();
+			pallet_session::GenesisConfig::<Runtime> {
+				keys: seeds
+					.iter()
+					.map(|&seed| {
+						let keys = SessionKeys {
+							aura: sr25519::Public::from_raw([seed; 32]).into(),
+							grandpa: ed25519::Public::from_raw([seed; 32]).into(),
+						};
+						(account(seed), account(seed), keys)
+					})
+					.collect(),
+			}
+			.assimilate_storage(&mut t)
+			.unwrap();
+		}
+
+		let mut t: sp_io::TestExternalities = t.into();
+		t.execute_with(|| put_storage_value(b"Aura", b"CurrentSlot", &[], Slot::from(slot)));
+		t
+	}
+
+	#[test]
+	fn author_is_the_validator_of_the_current_slot() {
+		with_validators(&[1, 2, 3], 7).execute_with(|| {
+			assert_eq!(AuraAuthor::current(), Some(account(2)));
+		});
+		with_validators(&[], 7).execute_with(|| {
+			assert_eq!(AuraAuthor::current(), None);
+		});
+	}
+
+	#[test]
+	fn fees_are_split_and_tips_go_to_the_author() {
+		with_validators(&[1, 2], 3).execute_with(|| {
+			let pot = Balances::free_balance(Treasury::account_id());
+			DealWithFees::on_unbalanceds(
+				vec![Balances::issue(10 * UNIT), Balances::issue(UNIT)].into_iter(),
+			);
+
+			assert_eq!(Balances::free_balance(Treasury::account_id()), pot + 8 * UNIT);
+			assert_eq!(Balances::free_balance(account(2)), 3 * UNIT);
+			assert_eq!(Balances::free_balance(account(1)), 0);
+		});
+	}
+
+	#[test]
+	fn author_share_goes_to_treasury_without_an_author() {
+		with_validators(&[], 3).execute_with(|| {
+			let pot = Balances::free_balance(Treasury::account_id());
+			DealWithFees::on_unbalanceds(
+				vec![Balances::issue(10 * UNIT), Balances::issue(UNIT)].into_iter(),
+			);
+
+			assert_eq!(Balances::free_balance(Treasury::account_id()), pot + 11 * UNIT);
+		});
+	}
+
+	#[test]
+	fn asset_fees_go_to_the_author() {
+		with_validators(&[1, 2], 3).execute_with(|| {
+			assert_ok!(Assets::force_create(Origin::root(), 7, account(9).into(), true, 1));
+
+			AssetFeesToAuthor::handle_credit(<Assets as Balanced<_>>::issue(7, 100));
+
+			assert_eq!(Assets::balance(7, account(2)), 100);
+			assert_eq!(Assets::total_supply(7), 100);
+		});
+	}
+
+	#[test]
+	fn evm_author_is_the_truncated_block_author() {
+		let no_digests = || sp_std::iter::empty::<(ConsensusEngineId, &[u8])>();
+		with_validators(&[1, 2, 3], 7).execute_with(|| {
+			assert_eq!(FindAuthorTruncated::find_author(no_digests()), Some(H160::repeat_byte(2)));
+		});
+		with_validators(&[], 7).execute_with(|| {
+			assert_eq!(FindAuthorTruncated::find_author(no_digests()), None);
+		});
+	}
+
+	#[test]
+	fn evm_sees_native_balances_in_wei() {
+		with_validators(&[], 0).execute_with(|| {
+			let address = H160::repeat_byte(9);
+			let account = HashedAddressMapping::<BlakeTwo256>::into_account_id(address);
+			let _ = Balances::deposit_creating(&account, 5 * UNIT / 2);
+
+			// What `eth_getBalance` returns: 2.5 ether.
+			assert_eq!(EVM::account_basic(&address).balance, U256::from(25) * U256::exp10(17));
+			// What `eth_gasPrice` returns: one microunit, or 1000 gwei.
+			assert_eq!(
+				<Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price(),
+				U256::from(1_000) * U256::exp10(9)
+			);
+		});
+	}
+
+	#[test]
+	fn evm_priority_fees_go_to_the_author() {
+		with_validators(&[1, 2, 3], 7).execute_with(|| {
+			let source = H160::repeat_byte(9);
+			let signer = AccountId::new([9; 32]);
+			let payer = HashedAddressMapping::<BlakeTwo256>::into_account_id(source);
+			let _ = Balances::deposit_creating(&payer, 100 * UNIT);
+			let _ = Balances::deposit_creating(&account(2), UNIT);
+			let _ = Balances::deposit_creating(&Treasury::account_id(), UNIT);
+			let issuance = Balances::total_issuance();
+
+			let base_fee = <Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price();
+			assert_ok!(EVM::call(
+				Origin::signed(signer),
+				source,
+				H160::repeat_byte(1),
+				vec![],
+				U256::zero(),
+				21_000,
+				base_fee * 2,
+				Some(base_fee),
+				None,
+				vec![],
+			));
+
+			// A transfer uses 21,000 gas, and the tip is capped by the fee over the base fee.
+			let gas_fee: Balance = (base_fee * 21_000).unique_saturated_into();
+			let tip = gas_fee;
+			assert_eq!(Balances::free_balance(&payer), 100 * UNIT - gas_fee - tip);
+			assert_eq!(Balances::free_balance(account(2)), UNIT + gas_fee / 5 + tip);
+			assert_eq!(Balances::free_balance(Treasury::account_id()), UNIT + gas_fee * 4 / 5);
+			assert_eq!(Balances::total_issuance(), issuance);
+
+			let digests = sp_std::iter::empty::<(ConsensusEngineId, &[u8])>();
+			let truncated = FindAuthorTruncated::find_author(digests).unwrap();
+			let unreachable = HashedAddressMapping::<BlakeTwo256>::into_account_id(truncated);
+			assert_eq!(Balances::free_balance(unreachable), 0);
+		});
+	}
+
+	#[test]
+	fn block_rewards_go_to_the_aura_author() {
+		with_validators(&[1, 2, 3], 7).execute_with(|| {
+			assert_ok!(BlockReward::set_reward(Origin::root(), UNIT));
+			let issuance = Balances::total_issuance();
+
+			BlockReward::on_initialize(1);
+			assert_eq!(Balances::free_balance(account(2)), UNIT);
+			assert_eq!(Balances::total_issuance(), issuance + UNIT);
+		});
+	}
+
+	#[test]
+	fn block_gas_limit_fits_in_a_normal_block() {
+		let gas_limit = BlockGasLimit::get().low_u64();
+		assert!(GasWeightMapping::gas_to_weight(gas_limit) <= max_normal());
+		assert!(GasWeightMapping::gas_to_weight(gas_limit + 1) > max_normal());
+		assert_eq!(
+			GasWeightMapping::weight_to_gas(GasWeightMapping::gas_to_weight(21_000)),
+			21_000
+		);
+	}
+
+	fn raw(bytes: &[u8]) -> Data {
+		Data::Raw(bytes.to_vec().try_into().unwrap())
+	}
+
+	fn identity(display: &[u8]) -> Box<IdentityInfo<crate::MaxAdditionalFields>> {
+		Box::new(IdentityInfo {
+			additional: Default::default(),
+			display: raw(display),
+			legal: Data::None,
+			web: Data::None,
+			riot: Data::None,
+			email: Data::None,
+			pgp_fingerprint: None,
+			image: Data::None,
+			twitter: Data::None,
+		})
+	}
+
+	#[test]
+	fn display_names_resolve_identities_and_sub_accounts() {
+		with_validators(&[], 0).execute_with(|| {
+			Balances::make_free_balance_be(&account(1), 10 * UNIT);
+			assert_eq!(display_name(&account(1)), None);
+
+			assert_ok!(Identity::set_identity(Origin::signed(account(1)), identity(b"Alice")));
+			assert_ok!(Identity::set_subs(
+				Origin::signed(account(1)),
+				vec![(account(2), raw(b"ops")), (account(3), Data::None)]
+			));
+			assert_eq!(
+				display_name(&account(1)),
+				Some(DisplayName { name: b"Alice".to_vec(), verified: false })
+			);
+			assert_eq!(display_name(&account(2)).unwrap().name, b"Alice/ops".to_vec());
+			assert_eq!(display_name(&account(3)).unwrap().name, b"Alice".to_vec());
+			assert_eq!(display_name(&account(4)), None);
+
+			// A judgement of the parent's identity vouches for its sub-accounts too.
+			assert_ok!(Identity::add_registrar(Origin::root(), account(9)));
+			assert_ok!(Identity::provide_judgement(
+				Origin::signed(account(9)),
+				0,
+				account(1).into(),
+				Judgement::Reasonable
+			));
+			assert!(display_name(&account(1)).unwrap().verified);
+			assert!(display_name(&account(2)).unwrap().verified);
+		});
+	}
+
+	#[test]
+	fn identities_without_a_raw_display_name_have_none() {
+		with_validators(&[], 0).execute_with(|| {
+			Balances::make_free_balance_be(&account(1), 10 * UNIT);
+			let mut info = identity(b"");
+			info.display = Data::Sha256([7; 32]);
+			assert_ok!(Identity::set_identity(Origin::signed(account(1)), info));
+
+			assert_eq!(display_name(&account(1)), None);
+		});
+	}
+
+	#[test]
+	fn full_block_costs_the_target_fee() {
+		assert_eq_error_rate!(WeightToFee::calc(&max_normal()), FULL_BLOCK_FEE, UNIT / 1_000);
+	}
+
+	#[test]
+	fn heavier_transactions_cost_more_than_proportionally() {
+		let fee = |weight| WeightToFee::calc(&weight);
+		let quarter = max_normal() / 4;
+		assert!(fee(2 * quarter) > 2 * fee(quarter));
+		assert!(fee(4 * quarter) > 2 * fee(2 * quarter));
+
+		// Half a block pays half of the linear term and a quarter of the quadratic one.
+		assert_eq_error_rate!(fee(max_normal() / 2), FULL_BLOCK_FEE * 3 / 8, UNIT / 1_000);
+	}
+
+	#[test]
+	fn transactions_cost_more_than_the_existential_deposit() {
+		assert!(WeightToFee::calc(&ExtrinsicBaseWeight::get()) > ExistentialDeposit::get());
+	}
+
+	#[test]
+	fn multiplier_is_stable_at_target_fullness() {
+		assert_eq!(after_blocks(Multiplier::one(), target(), 100), Multiplier::one());
+	}
+
+	#[test]
+	fn multiplier_rises_under_full_blocks_and_recovers() {
+		let blocks = 1_000;
+
+		// Each full block raises the multiplier.
+		let mut multiplier = Multiplier::one();
+		with_block_weight(max_normal(), || {
+			for _ in 0..blocks {
+				let next = FeeUpdate::convert(multiplier);
+				assert!(next > multiplier);
+				multiplier = next;
+			}
+		});
+		assert!(multiplier > Multiplier::saturating_from_rational(102, 100));
+
+		// Empty blocks bring it back down. The target is a quarter of a block, so an empty block is
+		// a third as far off it as a full one and recovering takes about three times as long.
+		let mut recovered_after = None;
+		with_block_weight(0, || {
+			for block in 1..=4 * blocks {
+				let next = FeeUpdate::convert(multiplier);
+				assert!(next < multiplier);
+				multiplier = next;
+				if multiplier <= Multiplier::one() {
+					recovered_after = Some(block);
+					break
+				}
+			}
+		});
+		let recovered_after = recovered_after.expect("the multiplier recovers");
+		assert!(recovered_after > 2 * blocks && recovered_after < 4 * blocks);
+	}
+
+	#[test]
+	fn multiplier_does_not_fall_below_minimum() {
+		let minimum = MinimumMultiplier::get();
+		assert_eq!(after_blocks(minimum, 0, 100), minimum);
+		assert!(after_blocks(Multiplier::one(), 0, 100) > minimum);
+	}
+}
@@ -2,21 +2,43 @@
 // `construct_runtime!` does a lot of recursion and requires us to increase the limit to 256.
 #![recursion_limit = "256"]
 
+pub mod apis;
+pub mod impls;
+pub mod precompiles;
+
 // Make the WASM binary available.
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_system::EnsureRoot;
+use pallet_ethereum::{Call::transact, Transaction as EthereumTransaction};
+use pallet_evm::{
+	Account as EVMAccount, EnsureAddressTruncated, FeeCalculator, HashedAddressMapping, Runner,
+};
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
+use pallet_session::historical as pallet_session_historical;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use sp_core::{
+	crypto::KeyTypeId,
+	u32_trait::{_1, _2, _3, _4, _5},
+	OpaqueMetadata, H160, H256, U256,
+};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
-	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	traits::{
+		AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto, Dispatchable,
+		Extrinsic as ExtrinsicT, IdentifyAccount, NumberFor, OpaqueKeys, PostDispatchInfoOf,
+		SaturatedConversion, Verify,
+	},
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+		TransactionValidityError,
+	},
+	ApplyExtrinsicResult, FixedPointNumber, MultiSignature, RuntimeDebug,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -26,19 +48,22 @@ use sp_version::RuntimeVersion;
 // A few exports that help ease life for downstream crates.
 pub use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{KeyOwnerProofSystem, Randomness, StorageInfo},
+	traits::{
+		Contains, EnsureOneOf, EqualPrivilegeOnly, InstanceFilter, KeyOwnerProofSystem, Nothing,
+		PalletInfoAccess, Randomness, StorageInfo,
+	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
-		IdentityFee, Weight,
+		DispatchClass, IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
-use pallet_transaction_payment::CurrencyAdapter;
+use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{Perbill, Permill, Perquintill};
 
 /// Import the template pallet.
 pub use pallet_template;
@@ -96,12 +121,13 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// The version of the runtime specification. A full node will not attempt to use its native
 	//   runtime in substitute for the on-chain Wasm runtime unless all of `spec_name`,
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
-	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
-	//   the compatible custom types.
-	spec_version: 100,
+	// Started at 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use the
+	//   compatible custom types. Bump it with every change to the runtime's storage or logic,
+	//   otherwise `set_code` rejects the upgrade and `on_runtime_upgrade` never runs.
+	spec_version: 123,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 3,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -121,6 +147,22 @@ pub const MINUTES: BlockNumber = 60_000 / (MILLISECS_PER_BLOCK as BlockNumber);
 pub const HOURS: BlockNumber = MINUTES * 60;
 pub const DAYS: BlockNumber = HOURS * 24;
 
+/// The ticker of the native token.
+pub const TOKEN_SYMBOL: &str = "UNIT";
+/// The number of decimal places of the native token. It matches Ether's, so that the EVM's wei
+/// are the token's smallest unit and Ethereum tooling shows balances and gas prices as they are.
+pub const TOKEN_DECIMALS: u8 = 18;
+
+// Balances are measured in units of the native token.
+pub const UNIT: Balance = 10u128.pow(TOKEN_DECIMALS as u32);
+pub const MILLIUNIT: Balance = UNIT / 1_000;
+pub const MICROUNIT: Balance = MILLIUNIT / 1_000;
+
+/// The deposit for `items` storage items taking up `bytes` bytes in total.
+pub const fn deposit(items: u32, bytes: u32) -> Balance {
+	items as Balance * 10 * MILLIUNIT + (bytes as Balance) * 10 * MICROUNIT
+}
+
 /// The version information used to identify this runtime when compiled natively.
 #[cfg(feature = "std")]
 pub fn native_version() -> NativeVersion {
@@ -128,23 +170,42 @@ pub fn native_version() -> NativeVersion {
 }
 
 const NORMAL_DISPATCH_RATIO: Perbill = Perbill::from_percent(75);
+/// The share of a block's weight that `on_initialize` is expected to use on average.
+const AVERAGE_ON_INITIALIZE_RATIO: Perbill = Perbill::from_percent(10);
+/// We allow for 2 seconds of compute with a 6 second average block time.
+const MAXIMUM_BLOCK_WEIGHT: Weight = 2 * WEIGHT_PER_SECOND;
 
 parameter_types! {
 	pub const Version: RuntimeVersion = VERSION;
 	pub const BlockHashCount: BlockNumber = 2400;
-	/// We allow for 2 seconds of compute with a 6 second average block time.
 	pub BlockWeights: frame_system::limits::BlockWeights = frame_system::limits::BlockWeights
-		::with_sensible_defaults(2 * WEIGHT_PER_SECOND, NORMAL_DISPATCH_RATIO);
+		::builder()
+		.base_block(BlockExecutionWeight::get())
+		.for_class(DispatchClass::all(), |weights| {
+			// The `BaseCallFilter` looks up the paused pallets and calls for every extrinsic.
+			weights.base_extrinsic = ExtrinsicBaseWeight::get() + RocksDbWeight::get().reads(2);
+		})
+		.for_class(DispatchClass::Normal, |weights| {
+			weights.max_total = Some(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT);
+		})
+		.for_class(DispatchClass::Operational, |weights| {
+			weights.max_total = Some(MAXIMUM_BLOCK_WEIGHT);
+			// Operational transactions may use the space normal ones leave free.
+			weights.reserved =
+				Some(MAXIMUM_BLOCK_WEIGHT - NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT);
+		})
+		.avg_block_initialization(AVERAGE_ON_INITIALIZE_RATIO)
+		.build_or_panic();
 	pub BlockLength: frame_system::limits::BlockLength = frame_system::limits::BlockLength
 		::max_with_normal_ratio(5 * 1024 * 1024, NORMAL_DISPATCH_RATIO);
-	pub const SS58Prefix: u8 = 42;
+	pub const SS58Prefix: u16 = 42;
 }
 
 // Configure FRAME pallets to include in runtime.
 
 impl frame_system::Config for Runtime {
-	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = frame_support::traits::Everything;
+	/// The basic call filter to use in dispatchable. Lets through all calls that are not paused.
+	type BaseCallFilter = pallet_transaction_pause::NonPausedCalls<Runtime>;
 	/// Block & extrinsics weights: base values and limits.
 	type BlockWeights = BlockWeights;
 	/// The maximum length of a block (in bytes).
@@ -187,7 +248,8 @@ impl frame_system::Config for Runtime {
 	type AccountData = pallet_balances::AccountData<Balance>;
 	/// Weight information for the extrinsics of this pallet.
 	type SystemWeightInfo = ();
-	/// This is used as an identifier of the chain. 42 is the generic substrate prefix.
+	/// This is used as an identifier of the chain and as the prefix of its SS58 addresses. 42 is
+	/// the generic substrate prefix.
 	type SS58Prefix = SS58Prefix;
 	/// The set code logic, just the default since we're not a parachain.
 	type OnSetCode = ();
@@ -195,13 +257,102 @@ impl frame_system::Config for Runtime {
 
 impl pallet_randomness_collective_flip::Config for Runtime {}
 
+parameter_types! {
+	pub const MinValidators: u32 = 1;
+}
+
+impl pallet_validator_set::Config for Runtime {
+	type Event = Event;
+	type AddRemoveOrigin = EnsureRootOrHalfCouncil;
+	type MinValidators = MinValidators;
+	type MaxValidators = MaxAuthorities;
+	type WeightInfo = pallet_validator_set::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// Sessions are rotated every ten minutes.
+	pub const Period: BlockNumber = 10 * MINUTES;
+	pub const Offset: BlockNumber = 0;
+}
+
+impl pallet_session::Config for Runtime {
+	type Event = Event;
+	type ValidatorId = AccountId;
+	type ValidatorIdOf = pallet_validator_set::ValidatorOf<Self>;
+	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
+	/// Record the root of every session's validator set so key ownership can be proven later.
+	type SessionManager = pallet_session_historical::NoteHistoricalRoot<Self, ValidatorSet>;
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_session::historical::Config for Runtime {
+	type FullIdentification = AccountId;
+	type FullIdentificationOf = pallet_validator_set::ValidatorOf<Self>;
+}
+
+impl pallet_offences::Config for Runtime {
+	type Event = Event;
+	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
+	/// There is no stake to slash, so offenders are disabled and removed from the validator set.
+	type OnOffenceHandler = ValidatorSet;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	Call: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = Call;
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::CreateSignedTransaction<C> for Runtime
+where
+	Call: From<C>,
+{
+	fn create_transaction<A: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Index,
+	) -> Option<(Call, <UncheckedExtrinsic as ExtrinsicT>::SignaturePayload)> {
+		// Make the transaction mortal, for the longest period the block hash count allows.
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		// The block number is already set to the block being built, the parent is one lower.
+		let current_block = System::block_number().saturated_into::<u64>().saturating_sub(1);
+		let extra: SignedExtra = (
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(0, None),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| A::sign(payload, public))?;
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (Address::Id(account), signature, extra)))
+	}
+}
+
 parameter_types! {
 	pub const MaxAuthorities: u32 = 32;
+	/// Equivocation reports stay valid for six sessions.
+	pub const ReportLongevity: u64 = 6 * Period::get() as u64;
 }
 
 impl pallet_aura::Config for Runtime {
 	type AuthorityId = AuraId;
-	type DisabledValidators = ();
+	type DisabledValidators = Session;
 	type MaxAuthorities = MaxAuthorities;
 }
 
@@ -209,7 +360,7 @@ impl pallet_grandpa::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
 
-	type KeyOwnerProofSystem = ();
+	type KeyOwnerProofSystem = Historical;
 
 	type KeyOwnerProof =
 		<Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(KeyTypeId, GrandpaId)>>::Proof;
@@ -219,7 +370,11 @@ impl pallet_grandpa::Config for Runtime {
 		GrandpaId,
 	)>>::IdentificationTuple;
 
-	type HandleEquivocation = ();
+	type HandleEquivocation = pallet_grandpa::EquivocationHandler<
+		Self::KeyOwnerIdentification,
+		Offences,
+		ReportLongevity,
+	>;
 
 	type WeightInfo = ();
 	type MaxAuthorities = MaxAuthorities;
@@ -239,6 +394,7 @@ impl pallet_timestamp::Config for Runtime {
 
 parameter_types! {
 	pub const ExistentialDeposit: u128 = 500;
+	/// Vesting, democracy votes and the like each lock an account's balance under their own id.
 	pub const MaxLocks: u32 = 50;
 }
 
@@ -257,16 +413,87 @@ impl pallet_balances::Config for Runtime {
 }
 
 parameter_types! {
-	pub const TransactionByteFee: Balance = 1;
+	pub const MinVestedTransfer: Balance = UNIT;
+}
+
+impl pallet_vesting::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+	/// All schedules of an account share a single balance lock, so they count once towards
+	/// `MaxLocks`.
+	const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
+/// Rewards go to the Aura author of the current slot. `BlockReward` must come after `Aura` in
+/// `construct_runtime!`, so that the slot is up to date when blocks are initialized.
+impl pallet_block_reward::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type FindAuthor = impls::AuraAuthor;
+	type AdminOrigin = EnsureRootOrHalfCouncil;
+	type MaxAuthors = MaxAuthorities;
+	type WeightInfo = pallet_block_reward::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const TransactionByteFee: Balance = 10 * MICROUNIT;
 	pub OperationalFeeMultiplier: u8 = 5;
+	/// The block fullness the fee multiplier steers towards. Fees rise while blocks are fuller
+	/// than this and fall while they are emptier.
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	/// How fast the fee multiplier reacts. Blocks that stay full raise fees by about 40% a day.
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(3, 100_000);
+	/// The fee multiplier never drops below this, so that empty blocks don't make fees vanish.
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000_000u128);
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction = CurrencyAdapter<Balances, impls::DealWithFees>;
 	type TransactionByteFee = TransactionByteFee;
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
-	type WeightToFee = IdentityFee<Balance>;
-	type FeeMultiplierUpdate = ();
+	type WeightToFee = impls::WeightToFee;
+	type FeeMultiplierUpdate =
+		TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+}
+
+/// Fees paid in an asset are converted from the native fee at the ratio of the asset's minimum
+/// balance to the native existential deposit.
+impl pallet_asset_tx_payment::Config for Runtime {
+	type Fungibles = Assets;
+	type OnChargeAssetTransaction = pallet_asset_tx_payment::FungiblesAdapter<
+		pallet_assets::BalanceToAssetBalance<Balances, Runtime, ConvertInto>,
+		impls::AssetFeesToAuthor,
+	>;
+}
+
+/// Identifier of an asset of the assets pallet.
+pub type AssetId = u32;
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 100 * UNIT;
+	pub const ApprovalDeposit: Balance = UNIT;
+	pub const AssetStringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 10 * UNIT;
+	pub const MetadataDepositPerByte: Balance = UNIT;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -274,9 +501,477 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+impl pallet_utility::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// A multisig operation stores the call hash, the depositor and the approvals so far.
+	pub const DepositBase: Balance = deposit(1, 88);
+	/// Each signatory adds 32 bytes to the approvals.
+	pub const DepositFactor: Balance = deposit(0, 32);
+	pub const MaxSignatories: u16 = 100;
+}
+
+impl pallet_multisig::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type DepositBase = DepositBase;
+	type DepositFactor = DepositFactor;
+	type MaxSignatories = MaxSignatories;
+	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// One storage item of the list of proxies, which is 8 bytes when empty.
+	pub const ProxyDepositBase: Balance = deposit(1, 8);
+	/// Each proxy adds 33 bytes: the proxy's account and its `ProxyType`.
+	pub const ProxyDepositFactor: Balance = deposit(0, 33);
+	pub const MaxProxies: u16 = 32;
+	pub const AnnouncementDepositBase: Balance = deposit(1, 8);
+	/// Each announcement adds 68 bytes: the proxy's account, the call hash and the block number.
+	pub const AnnouncementDepositFactor: Balance = deposit(0, 68);
+	pub const MaxPending: u16 = 32;
+}
+
+/// The calls a proxy may make on behalf of the account that added it.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	MaxEncodedLen,
+	scale_info::TypeInfo,
+)]
+pub enum ProxyType {
+	/// Any call.
+	Any,
+	/// Any call that can't move funds out of the account.
+	NonTransfer,
+	/// Calls of the template pallet, alone or in batches. This includes `transfer_something`,
+	/// which moves the account's entry and its deposit to another account.
+	Template,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			// An allowlist, so that the calls of new pallets are not allowed by accident.
+			// Multisig calls spend the funds of the shared account and sudo dispatches any call
+			// as root, so neither is on it.
+			ProxyType::NonTransfer => match c {
+				// Moves the entry and its deposit to the destination.
+				Call::TemplateModule(pallet_template::Call::transfer_something { .. }) => false,
+				Call::System(..) |
+				Call::Timestamp(..) |
+				Call::ValidatorSet(..) |
+				Call::Session(..) |
+				Call::Grandpa(..) |
+				Call::Council(..) |
+				Call::Preimage(..) |
+				Call::Scheduler(..) |
+				Call::Democracy(..) |
+				Call::Treasury(..) |
+				Call::Utility(..) |
+				Call::Proxy(..) |
+				Call::TransactionPause(..) |
+				Call::Identity(..) |
+				Call::Vesting(pallet_vesting::Call::vest { .. }) |
+				Call::Vesting(pallet_vesting::Call::vest_other { .. }) |
+				Call::BlockReward(..) |
+				Call::TemplateModule(..) => true,
+				_ => false,
+			},
+			// The calls in a batch are filtered one by one, so batches can only hold template
+			// calls too. `as_derivative` would reach the template entries of derivative accounts.
+			ProxyType::Template => matches!(
+				c,
+				Call::TemplateModule(..) |
+					Call::Utility(pallet_utility::Call::batch { .. }) |
+					Call::Utility(pallet_utility::Call::batch_all { .. })
+			),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			_ => false,
+		}
+	}
+}
+
+impl pallet_proxy::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+	type MaxPending = MaxPending;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+/// The pallets needed to produce blocks and to recover from an emergency, which can't be paused.
+/// Governance is among them, as it is the only way to `PauseOrigin` and to a runtime upgrade once
+/// sudo is removed.
+pub struct UnpausablePallets;
+
+impl Contains<Vec<u8>> for UnpausablePallets {
+	fn contains(pallet_name: &Vec<u8>) -> bool {
+		[
+			System::name(),
+			Sudo::name(),
+			Timestamp::name(),
+			Council::name(),
+			Democracy::name(),
+			Scheduler::name(),
+			Preimage::name(),
+		]
+		.iter()
+		.any(|name| name.as_bytes() == &pallet_name[..])
+	}
+}
+
+impl pallet_transaction_pause::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type PauseOrigin = EnsureRootOrHalfCouncil;
+	type UnpausablePallets = UnpausablePallets;
+	type WeightInfo = pallet_transaction_pause::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+}
+
+type CouncilCollective = pallet_collective::Instance1;
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+/// Root, or more than half of the council. Used for privileged calls that used to be sudo only.
+pub type EnsureRootOrHalfCouncil = EnsureOneOf<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>,
+>;
+
+parameter_types! {
+	/// An identity with no additional fields takes up 258 bytes.
+	pub const BasicDeposit: Balance = deposit(1, 258);
+	/// Each additional field takes up to 66 bytes.
+	pub const FieldDeposit: Balance = deposit(0, 66);
+	/// A sub-account's entries take up 53 bytes.
+	pub const SubAccountDeposit: Balance = deposit(1, 53);
+	pub const MaxSubAccounts: u32 = 100;
+	pub const MaxAdditionalFields: u32 = 100;
+	pub const MaxRegistrars: u32 = 20;
+}
+
+impl pallet_identity::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BasicDeposit = BasicDeposit;
+	type FieldDeposit = FieldDeposit;
+	type SubAccountDeposit = SubAccountDeposit;
+	type MaxSubAccounts = MaxSubAccounts;
+	type MaxAdditionalFields = MaxAdditionalFields;
+	type MaxRegistrars = MaxRegistrars;
+	/// Deposits of identities killed for being offensive go to the treasury.
+	type Slashed = Treasury;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type RegistrarOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const PreimageMaxSize: u32 = 4096 * 1024;
+	pub const PreimageBaseDeposit: Balance = UNIT;
+	pub const PreimageByteDeposit: Balance = MILLIUNIT;
+}
+
+impl pallet_preimage::Config for Runtime {
+	type Event = Event;
+	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<AccountId>;
+	type MaxSize = PreimageMaxSize;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
+	pub const MaxScheduledPerBlock: u32 = 50;
+	/// Scheduled calls whose preimage is missing are retried ten blocks later.
+	pub const NoPreimagePostponement: Option<BlockNumber> = Some(10);
+}
+
+impl pallet_scheduler::Config for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PreimageProvider = Preimage;
+	type NoPreimagePostponement = NoPreimagePostponement;
+}
+
+parameter_types! {
+	pub const LaunchPeriod: BlockNumber = 7 * DAYS;
+	pub const VotingPeriod: BlockNumber = 7 * DAYS;
+	pub const FastTrackVotingPeriod: BlockNumber = 3 * HOURS;
+	pub const EnactmentPeriod: BlockNumber = DAYS;
+	pub const CooloffPeriod: BlockNumber = 7 * DAYS;
+	pub const MinimumDeposit: Balance = 100 * UNIT;
+	pub const InstantAllowed: bool = true;
+	pub const MaxVotes: u32 = 100;
+	pub const MaxProposals: u32 = 100;
+}
+
+impl pallet_democracy::Config for Runtime {
+	type Proposal = Call;
+	type Event = Event;
+	type Currency = Balances;
+	type EnactmentPeriod = EnactmentPeriod;
+	type LaunchPeriod = LaunchPeriod;
+	type VotingPeriod = VotingPeriod;
+	type VoteLockingPeriod = EnactmentPeriod;
+	type MinimumDeposit = MinimumDeposit;
+	/// A majority of the council can have the next scheduled referendum be a straight
+	/// majority-carries vote.
+	type ExternalOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _2, AccountId, CouncilCollective>;
+	/// Three quarters of the council can have the next scheduled referendum be a simple
+	/// majority-carries vote.
+	type ExternalMajorityOrigin =
+		pallet_collective::EnsureProportionAtLeast<_3, _4, AccountId, CouncilCollective>;
+	/// A unanimous council can have the next scheduled referendum be a straight default-carries
+	/// (negative turnout biased) vote.
+	type ExternalDefaultOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>;
+	/// Three quarters of the council can fast track an external proposal.
+	type FastTrackOrigin =
+		pallet_collective::EnsureProportionAtLeast<_3, _4, AccountId, CouncilCollective>;
+	/// A unanimous council can put an external proposal to a vote straight away.
+	type InstantOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>;
+	type InstantAllowed = InstantAllowed;
+	type FastTrackVotingPeriod = FastTrackVotingPeriod;
+	/// Three quarters of the council can cancel a referendum.
+	type CancellationOrigin =
+		pallet_collective::EnsureProportionAtLeast<_3, _4, AccountId, CouncilCollective>;
+	type BlacklistOrigin = EnsureRoot<AccountId>;
+	/// Root or a unanimous council can cancel a public proposal.
+	type CancelProposalOrigin = EnsureOneOf<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>,
+	>;
+	/// Any council member can veto an external proposal, once per cool-off period.
+	type VetoOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type CooloffPeriod = CooloffPeriod;
+	type PreimageByteDeposit = PreimageByteDeposit;
+	type OperationalPreimageOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type Slash = ();
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type MaxVotes = MaxVotes;
+	type WeightInfo = pallet_democracy::weights::SubstrateWeight<Runtime>;
+	type MaxProposals = MaxProposals;
+}
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = UNIT;
+	pub const SpendPeriod: BlockNumber = DAYS;
+	/// Funds left over after a spend period stay in the treasury.
+	pub const Burn: Permill = Permill::zero();
+	pub const MaxApprovals: u32 = 100;
+}
+
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	/// Spend proposals are approved by root or three fifths of the council.
+	type ApproveOrigin = EnsureOneOf<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_3, _5, AccountId, CouncilCollective>,
+	>;
+	type RejectOrigin = EnsureRootOrHalfCouncil;
+	type Event = Event;
+	type OnSlash = Treasury;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type ProposalBondMaximum = ();
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type SpendFunds = ();
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+	type MaxApprovals = MaxApprovals;
+}
+
+/// Whether the dry-run RPCs return the debug buffer of the contracts they execute. Contracts only
+/// write to it when it is requested, which never happens during block production.
+const CONTRACTS_DEBUG_OUTPUT: bool = true;
+
+parameter_types! {
+	pub const DepositPerItem: Balance = deposit(1, 0);
+	pub const DepositPerByte: Balance = deposit(0, 1);
+	/// The deposit kept on each contract's account so that it can't be reaped.
+	pub const ContractDeposit: Balance = deposit(1, 0);
+	/// Removed contracts are cleaned up lazily, with at most a tenth of a block.
+	pub DeletionWeightLimit: Weight = Perbill::from_percent(10) * BlockWeights::get().max_block;
+	pub const DeletionQueueDepth: u32 = 128;
+	pub Schedule: pallet_contracts::Schedule<Runtime> = Default::default();
+}
+
+impl pallet_contracts::Config for Runtime {
+	type Time = Timestamp;
+	type Randomness = RandomnessCollectiveFlip;
+	type Currency = Balances;
+	type Event = Event;
+	type Call = Call;
+	/// Contracts can't dispatch runtime calls. Allow them selectively by replacing `Nothing` with
+	/// a filter over `Call`.
+	type CallFilter = Nothing;
+	type DepositPerItem = DepositPerItem;
+	type DepositPerByte = DepositPerByte;
+	type ContractDeposit = ContractDeposit;
+	type CallStack = [pallet_contracts::Frame<Self>; 31];
+	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
+	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Self>;
+	type ChainExtension = ();
+	type DeletionQueueDepth = DeletionQueueDepth;
+	type DeletionWeightLimit = DeletionWeightLimit;
+	type Schedule = Schedule;
+	type AddressGenerator = pallet_contracts::DefaultAddressGenerator;
+}
+
+parameter_types! {
+	/// The EIP-155 chain id, which Ethereum transactions are signed for. Unlike 42, which is
+	/// Kovan's, it is not used by a public network, so transactions cannot be replayed on one. A
+	/// network built from this template should register its own id.
+	pub const ChainId: u64 = 7_411_893;
+	/// As much gas as fits in the weight of a block of normal transactions.
+	pub BlockGasLimit: U256 =
+		U256::from(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT / impls::WEIGHT_PER_GAS);
+	pub PrecompilesValue: precompiles::EthereumPrecompiles<Runtime> =
+		precompiles::EthereumPrecompiles::new();
+}
+
+/// EVM addresses are mapped to accounts by hashing, so every address has an account that can hold
+/// balance without a private key for it. A signed origin can act as the address made of the
+/// first 20 bytes of its account.
+impl pallet_evm::Config for Runtime {
+	type FeeCalculator = BaseFee;
+	type GasWeightMapping = impls::GasWeightMapping;
+	type BlockHashMapping = pallet_ethereum::EthereumBlockHashMapping<Self>;
+	type CallOrigin = EnsureAddressTruncated;
+	type WithdrawOrigin = EnsureAddressTruncated;
+	type AddressMapping = HashedAddressMapping<BlakeTwo256>;
+	type Currency = Balances;
+	type Event = Event;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type PrecompilesType = precompiles::EthereumPrecompiles<Self>;
+	type PrecompilesValue = PrecompilesValue;
+	type ChainId = ChainId;
+	type BlockGasLimit = BlockGasLimit;
+	/// Gas fees are split between the treasury and the block author like transaction fees.
+	type OnChargeTransaction = impls::EvmFees;
+	type FindAuthor = impls::FindAuthorTruncated;
+}
+
+impl pallet_ethereum::Config for Runtime {
+	type Event = Event;
+	type StateRoot = pallet_ethereum::IntermediateStateRoot;
+}
+
+parameter_types! {
+	pub IsActive: bool = true;
+	/// A plain transfer of 21 000 gas costs 21 milliunits at the default base fee.
+	pub DefaultBaseFeePerGas: U256 = U256::from(MICROUNIT);
+}
+
+/// Moves the base fee up while blocks are more than half full and down while they are less,
+/// as in EIP-1559.
+pub struct BaseFeeThreshold;
+
+impl pallet_base_fee::BaseFeeThreshold for BaseFeeThreshold {
+	fn lower() -> Permill {
+		Permill::zero()
+	}
+
+	fn ideal() -> Permill {
+		Permill::from_percent(50)
+	}
+
+	fn upper() -> Permill {
+		Permill::from_percent(100)
+	}
+}
+
+impl pallet_base_fee::Config for Runtime {
+	type Event = Event;
+	type Threshold = BaseFeeThreshold;
+	type IsActive = IsActive;
+	type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
+}
+
+parameter_types! {
+	pub const TemplateStorageDeposit: Balance = 10 * MILLIUNIT;
+	pub const MaxValues: u32 = 100;
+	pub const UnsignedInterval: BlockNumber = 10;
+	pub const UnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type Event = Event;
+	type Currency = Balances;
+	type StorageDeposit = TemplateStorageDeposit;
+	type MaxValues = MaxValues;
+	type AuthorityId = pallet_template::crypto::TemplateAuthId;
+	type Authorities = ValidatorSet;
+	type UnsignedInterval = UnsignedInterval;
+	type UnsignedPriority = UnsignedPriority;
+	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -289,11 +984,36 @@ construct_runtime!(
 		System: frame_system,
 		RandomnessCollectiveFlip: pallet_randomness_collective_flip,
 		Timestamp: pallet_timestamp,
+		// Session and the validator set must come before Aura and Grandpa, so that the session
+		// keys are in place when their genesis and session hooks run.
+		ValidatorSet: pallet_validator_set,
+		Session: pallet_session,
+		Historical: pallet_session_historical::{Pallet},
+		Offences: pallet_offences,
 		Aura: pallet_aura,
 		Grandpa: pallet_grandpa,
 		Balances: pallet_balances,
 		TransactionPayment: pallet_transaction_payment,
+		AssetTxPayment: pallet_asset_tx_payment,
+		Assets: pallet_assets,
+		// Sudo is kept until governance has taken over. See the README for how to remove it.
 		Sudo: pallet_sudo,
+		Council: pallet_collective::<Instance1>,
+		Preimage: pallet_preimage,
+		Scheduler: pallet_scheduler,
+		Democracy: pallet_democracy,
+		Treasury: pallet_treasury,
+		Contracts: pallet_contracts,
+		EVM: pallet_evm,
+		Ethereum: pallet_ethereum,
+		BaseFee: pallet_base_fee,
+		Utility: pallet_utility,
+		Multisig: pallet_multisig,
+		Proxy: pallet_proxy,
+		TransactionPause: pallet_transaction_pause,
+		Identity: pallet_identity,
+		Vesting: pallet_vesting,
+		BlockReward: pallet_block_reward,
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template,
 	}
@@ -313,10 +1033,16 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
 );
-/// Unchecked extrinsic type as expected by this runtime.
-pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
+/// Unchecked extrinsic type as expected by this runtime. Ethereum transactions are unsigned
+/// extrinsics that carry their own signature.
+pub type UncheckedExtrinsic =
+	fp_self_contained::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
+/// Extrinsic type that has already been checked.
+pub type CheckedExtrinsic = fp_self_contained::CheckedExtrinsic<AccountId, Call, SignedExtra, H160>;
+/// The payload being signed in transactions.
+pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -326,6 +1052,76 @@ pub type Executive = frame_executive::Executive<
 	AllPallets,
 >;
 
+/// Wraps Ethereum transactions submitted over the `eth_*` RPCs into extrinsics.
+pub struct TransactionConverter;
+
+impl fp_rpc::ConvertTransaction<UncheckedExtrinsic> for TransactionConverter {
+	fn convert_transaction(&self, transaction: EthereumTransaction) -> UncheckedExtrinsic {
+		UncheckedExtrinsic::new_unsigned(
+			pallet_ethereum::Call::<Runtime>::transact { transaction }.into(),
+		)
+	}
+}
+
+impl fp_rpc::ConvertTransaction<opaque::UncheckedExtrinsic> for TransactionConverter {
+	fn convert_transaction(&self, transaction: EthereumTransaction) -> opaque::UncheckedExtrinsic {
+		let extrinsic = UncheckedExtrinsic::new_unsigned(
+			pallet_ethereum::Call::<Runtime>::transact { transaction }.into(),
+		);
+		let encoded = extrinsic.encode();
+		opaque::UncheckedExtrinsic::decode(&mut &encoded[..])
+			.expect("Encoded extrinsic is always valid")
+	}
+}
+
+impl fp_self_contained::SelfContainedCall for Call {
+	/// The sender recovered from the Ethereum signature.
+	type SignedInfo = H160;
+
+	fn is_self_contained(&self) -> bool {
+		match self {
+			Call::Ethereum(call) => call.is_self_contained(),
+			_ => false,
+		}
+	}
+
+	fn check_self_contained(&self) -> Option<Result<Self::SignedInfo, TransactionValidityError>> {
+		match self {
+			Call::Ethereum(call) => call.check_self_contained(),
+			_ => None,
+		}
+	}
+
+	fn validate_self_contained(&self, info: &Self::SignedInfo) -> Option<TransactionValidity> {
+		match self {
+			Call::Ethereum(call) => call.validate_self_contained(info),
+			_ => None,
+		}
+	}
+
+	fn pre_dispatch_self_contained(
+		&self,
+		info: &Self::SignedInfo,
+	) -> Option<Result<(), TransactionValidityError>> {
+		match self {
+			Call::Ethereum(call) => call.pre_dispatch_self_contained(info),
+			_ => None,
+		}
+	}
+
+	fn apply_self_contained(
+		self,
+		info: Self::SignedInfo,
+	) -> Option<sp_runtime::DispatchResultWithInfo<PostDispatchInfoOf<Self>>> {
+		match self {
+			call @ Call::Ethereum(pallet_ethereum::Call::transact { .. }) => Some(
+				call.dispatch(Origin::from(pallet_ethereum::RawOrigin::EthereumTransaction(info))),
+			),
+			_ => None,
+		}
+	}
+}
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -374,6 +1170,10 @@ impl_runtime_apis! {
 			tx: <Block as BlockT>::Extrinsic,
 			block_hash: <Block as BlockT>::Hash,
 		) -> TransactionValidity {
+			// Paused calls would only fail once dispatched. Keep them out of the pool instead.
+			if impls::is_paused(&tx.0.function) {
+				return InvalidTransaction::Call.into()
+			}
 			Executive::validate_transaction(source, tx, block_hash)
 		}
 	}
@@ -416,23 +1216,27 @@ impl_runtime_apis! {
 		}
 
 		fn submit_report_equivocation_unsigned_extrinsic(
-			_equivocation_proof: fg_primitives::EquivocationProof<
+			equivocation_proof: fg_primitives::EquivocationProof<
 				<Block as BlockT>::Hash,
 				NumberFor<Block>,
 			>,
-			_key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
+			key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
 		) -> Option<()> {
-			None
+			let key_owner_proof = key_owner_proof.decode()?;
+
+			Grandpa::submit_unsigned_equivocation_report(
+				equivocation_proof,
+				key_owner_proof,
+			)
 		}
 
 		fn generate_key_ownership_proof(
 			_set_id: fg_primitives::SetId,
-			_authority_id: GrandpaId,
+			authority_id: GrandpaId,
 		) -> Option<fg_primitives::OpaqueKeyOwnershipProof> {
-			// NOTE: this is the only implementation possible since we've
-			// defined our key owner proof type as a bottom type (i.e. a type
-			// with no values).
-			None
+			Historical::prove((fg_primitives::KEY_TYPE, authority_id))
+				.map(|p| p.encode())
+				.map(fg_primitives::OpaqueKeyOwnershipProof::new)
 		}
 	}
 
@@ -457,6 +1261,246 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_contracts_rpc_runtime_api::ContractsApi<Block, AccountId, Balance, BlockNumber, Hash>
+		for Runtime
+	{
+		fn call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: u64,
+			storage_deposit_limit: Option<Balance>,
+			input_data: Vec<u8>,
+		) -> pallet_contracts_primitives::ContractExecResult<Balance> {
+			Contracts::bare_call(
+				origin,
+				dest,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				input_data,
+				CONTRACTS_DEBUG_OUTPUT,
+			)
+		}
+
+		fn instantiate(
+			origin: AccountId,
+			value: Balance,
+			gas_limit: u64,
+			storage_deposit_limit: Option<Balance>,
+			code: pallet_contracts_primitives::Code<Hash>,
+			data: Vec<u8>,
+			salt: Vec<u8>,
+		) -> pallet_contracts_primitives::ContractInstantiateResult<AccountId, Balance> {
+			Contracts::bare_instantiate(
+				origin,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				code,
+				data,
+				salt,
+				CONTRACTS_DEBUG_OUTPUT,
+			)
+		}
+
+		fn upload_code(
+			origin: AccountId,
+			code: Vec<u8>,
+			storage_deposit_limit: Option<Balance>,
+		) -> pallet_contracts_primitives::CodeUploadResult<Hash, Balance> {
+			Contracts::bare_upload_code(origin, code, storage_deposit_limit)
+		}
+
+		fn get_storage(
+			address: AccountId,
+			key: [u8; 32],
+		) -> pallet_contracts_primitives::GetStorageResult {
+			Contracts::get_storage(address, key)
+		}
+	}
+
+	impl pallet_template_rpc_runtime_api::TemplateApi<Block, AccountId> for Runtime {
+		fn get_something(who: AccountId) -> Option<u32> {
+			TemplateModule::value_of(&who)
+		}
+	}
+
+	impl apis::AssetsApi<Block, AccountId, AssetId, Balance> for Runtime {
+		fn balance(asset: AssetId, who: AccountId) -> Balance {
+			Assets::balance(asset, who)
+		}
+
+		fn total_supply(asset: AssetId) -> Option<Balance> {
+			Assets::maybe_total_supply(asset)
+		}
+	}
+
+	impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
+		fn chain_id() -> u64 {
+			<Runtime as pallet_evm::Config>::ChainId::get()
+		}
+
+		fn account_basic(address: H160) -> EVMAccount {
+			EVM::account_basic(&address)
+		}
+
+		fn gas_price() -> U256 {
+			<Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price()
+		}
+
+		fn account_code_at(address: H160) -> Vec<u8> {
+			EVM::account_codes(address)
+		}
+
+		fn author() -> H160 {
+			<pallet_evm::Pallet<Runtime>>::find_author()
+		}
+
+		fn storage_at(address: H160, index: U256) -> H256 {
+			let mut tmp = [0u8; 32];
+			index.to_big_endian(&mut tmp);
+			EVM::account_storages(address, H256::from_slice(&tmp[..]))
+		}
+
+		fn call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+		) -> Result<pallet_evm::CallInfo, sp_runtime::DispatchError> {
+			let config = if estimate {
+				let mut config = <Runtime as pallet_evm::Config>::config().clone();
+				config.estimate = true;
+				Some(config)
+			} else {
+				None
+			};
+
+			<Runtime as pallet_evm::Config>::Runner::call(
+				from,
+				to,
+				data,
+				value,
+				gas_limit.low_u64(),
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				config.as_ref().unwrap_or_else(|| <Runtime as pallet_evm::Config>::config()),
+			).map_err(|err| err.into())
+		}
+
+		fn create(
+			from: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+		) -> Result<pallet_evm::CreateInfo, sp_runtime::DispatchError> {
+			let config = if estimate {
+				let mut config = <Runtime as pallet_evm::Config>::config().clone();
+				config.estimate = true;
+				Some(config)
+			} else {
+				None
+			};
+
+			<Runtime as pallet_evm::Config>::Runner::create(
+				from,
+				data,
+				value,
+				gas_limit.low_u64(),
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				config.as_ref().unwrap_or_else(|| <Runtime as pallet_evm::Config>::config()),
+			).map_err(|err| err.into())
+		}
+
+		fn current_transaction_statuses() -> Option<Vec<fp_rpc::TransactionStatus>> {
+			Ethereum::current_transaction_statuses()
+		}
+
+		fn current_block() -> Option<pallet_ethereum::Block> {
+			Ethereum::current_block()
+		}
+
+		fn current_receipts() -> Option<Vec<pallet_ethereum::Receipt>> {
+			Ethereum::current_receipts()
+		}
+
+		fn current_all() -> (
+			Option<pallet_ethereum::Block>,
+			Option<Vec<pallet_ethereum::Receipt>>,
+			Option<Vec<fp_rpc::TransactionStatus>>,
+		) {
+			(
+				Ethereum::current_block(),
+				Ethereum::current_receipts(),
+				Ethereum::current_transaction_statuses(),
+			)
+		}
+
+		fn extrinsic_filter(
+			xts: Vec<<Block as BlockT>::Extrinsic>,
+		) -> Vec<EthereumTransaction> {
+			xts.into_iter().filter_map(|xt| match xt.0.function {
+				Call::Ethereum(transact { transaction }) => Some(transaction),
+				_ => None,
+			}).collect()
+		}
+
+		fn elasticity() -> Option<Permill> {
+			Some(BaseFee::elasticity())
+		}
+	}
+
+	impl fp_rpc::ConvertTransactionRuntimeApi<Block> for Runtime {
+		fn convert_transaction(transaction: EthereumTransaction) -> <Block as BlockT>::Extrinsic {
+			UncheckedExtrinsic::new_unsigned(
+				pallet_ethereum::Call::<Runtime>::transact { transaction }.into(),
+			)
+		}
+	}
+
+	impl identity_rpc_runtime_api::IdentityApi<Block, AccountId> for Runtime {
+		fn display_name(who: AccountId) -> Option<identity_rpc_runtime_api::DisplayName> {
+			impls::display_name(&who)
+		}
+	}
+
+	impl apis::ChainPropertiesApi<Block> for Runtime {
+		fn properties() -> apis::ChainProperties {
+			apis::ChainProperties {
+				token_symbol: TOKEN_SYMBOL.as_bytes().to_vec(),
+				token_decimals: TOKEN_DECIMALS,
+				ss58_format: SS58Prefix::get(),
+			}
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	impl frame_try_runtime::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade() -> (Weight, Weight) {
+			// The unwrap is deliberate: a failing pre or post upgrade check should abort the
+			// run right here, with a backtrace, instead of being reported back as a weight.
+			let weight = Executive::try_runtime_upgrade().unwrap();
+			(weight, BlockWeights::get().max_block)
+		}
+
+		fn execute_block_no_check(block: Block) -> Weight {
+			Executive::execute_block_no_check(block)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -472,9 +1516,24 @@ impl_runtime_apis! {
 
 			list_benchmark!(list, extra, frame_benchmarking, BaselineBench::<Runtime>);
 			list_benchmark!(list, extra, frame_system, SystemBench::<Runtime>);
+			list_benchmark!(list, extra, pallet_assets, Assets);
 			list_benchmark!(list, extra, pallet_balances, Balances);
+			list_benchmark!(list, extra, pallet_block_reward, BlockReward);
+			list_benchmark!(list, extra, pallet_collective, Council);
+			list_benchmark!(list, extra, pallet_contracts, Contracts);
+			list_benchmark!(list, extra, pallet_democracy, Democracy);
+			list_benchmark!(list, extra, pallet_identity, Identity);
+			list_benchmark!(list, extra, pallet_multisig, Multisig);
+			list_benchmark!(list, extra, pallet_preimage, Preimage);
+			list_benchmark!(list, extra, pallet_proxy, Proxy);
+			list_benchmark!(list, extra, pallet_scheduler, Scheduler);
 			list_benchmark!(list, extra, pallet_timestamp, Timestamp);
 			list_benchmark!(list, extra, pallet_template, TemplateModule);
+			list_benchmark!(list, extra, pallet_transaction_pause, TransactionPause);
+			list_benchmark!(list, extra, pallet_treasury, Treasury);
+			list_benchmark!(list, extra, pallet_utility, Utility);
+			list_benchmark!(list, extra, pallet_validator_set, ValidatorSet);
+			list_benchmark!(list, extra, pallet_vesting, Vesting);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -510,11 +1569,277 @@ impl_runtime_apis! {
 
 			add_benchmark!(params, batches, frame_benchmarking, BaselineBench::<Runtime>);
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
+			add_benchmark!(params, batches, pallet_assets, Assets);
 			add_benchmark!(params, batches, pallet_balances, Balances);
+			add_benchmark!(params, batches, pallet_block_reward, BlockReward);
+			add_benchmark!(params, batches, pallet_collective, Council);
+			add_benchmark!(params, batches, pallet_contracts, Contracts);
+			add_benchmark!(params, batches, pallet_democracy, Democracy);
+			add_benchmark!(params, batches, pallet_identity, Identity);
+			add_benchmark!(params, batches, pallet_multisig, Multisig);
+			add_benchmark!(params, batches, pallet_preimage, Preimage);
+			add_benchmark!(params, batches, pallet_proxy, Proxy);
+			add_benchmark!(params, batches, pallet_scheduler, Scheduler);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			add_benchmark!(params, batches, pallet_template, TemplateModule);
+			add_benchmark!(params, batches, pallet_transaction_pause, TransactionPause);
+			add_benchmark!(params, batches, pallet_treasury, Treasury);
+			add_benchmark!(params, batches, pallet_utility, Utility);
+			add_benchmark!(params, batches, pallet_validator_set, ValidatorSet);
+			add_benchmark!(params, batches, pallet_vesting, Vesting);
 
 			Ok(batches)
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{
+		assert_noop, assert_ok,
+		traits::{GenesisBuild, WrapperKeepOpaque},
+	};
+
+	fn account(seed: u8) -> AccountId {
+		AccountId::new([seed; 32])
+	}
+
+	fn transfer() -> Call {
+		Call::Balances(pallet_balances::Call::transfer { dest: account(3).into(), value: UNIT })
+	}
+
+	fn asset_transfer() -> Call {
+		Call::Assets(pallet_assets::Call::transfer { id: 0, target: account(3).into(), amount: 1 })
+	}
+
+	fn template() -> Call {
+		Call::TemplateModule(pallet_template::Call::do_something { something: 42 })
+	}
+
+	fn remark() -> Call {
+		Call::System(frame_system::Call::remark { remark: vec![] })
+	}
+
+	fn batch(calls: Vec<Call>) -> Call {
+		Call::Utility(pallet_utility::Call::batch { calls })
+	}
+
+	#[test]
+	fn any_proxy_allows_every_call() {
+		for call in [transfer(), asset_transfer(), template(), remark()] {
+			assert!(ProxyType::Any.filter(&call));
+		}
+	}
+
+	#[test]
+	fn non_transfer_proxy_rejects_calls_that_move_funds() {
+		assert!(!ProxyType::NonTransfer.filter(&transfer()));
+		assert!(!ProxyType::NonTransfer.filter(&asset_transfer()));
+		let schedule = pallet_vesting::VestingInfo::new(UNIT, UNIT, 0);
+		assert!(!ProxyType::NonTransfer.filter(&Call::Vesting(
+			pallet_vesting::Call::vested_transfer { target: account(3).into(), schedule }
+		)));
+		assert!(ProxyType::NonTransfer.filter(&Call::Vesting(pallet_vesting::Call::vest {})));
+		assert!(ProxyType::NonTransfer.filter(&template()));
+		assert!(ProxyType::NonTransfer.filter(&remark()));
+	}
+
+	#[test]
+	fn non_transfer_proxy_rejects_calls_that_move_funds_indirectly() {
+		// The template entry's deposit moves with it.
+		assert!(!ProxyType::NonTransfer.filter(&Call::TemplateModule(
+			pallet_template::Call::transfer_something { dest: account(3) }
+		)));
+		// A 1-of-n multisig spends the shared account's funds without anyone else's approval.
+		assert!(!ProxyType::NonTransfer.filter(&Call::Multisig(
+			pallet_multisig::Call::as_multi_threshold_1 {
+				other_signatories: vec![account(3)],
+				call: Box::new(transfer()),
+			}
+		)));
+		// Sudo dispatches as root, where the proxy's filter doesn't apply.
+		assert!(!ProxyType::NonTransfer.filter(&Call::Sudo(pallet_sudo::Call::sudo {
+			call: Box::new(Call::Balances(pallet_balances::Call::force_transfer {
+				source: account(1).into(),
+				dest: account(3).into(),
+				value: UNIT,
+			})),
+		})));
+		assert!(ProxyType::Template.filter(&Call::TemplateModule(
+			pallet_template::Call::transfer_something { dest: account(3) }
+		)));
+	}
+
+	#[test]
+	fn template_proxy_only_allows_template_calls() {
+		assert!(ProxyType::Template.filter(&template()));
+		assert!(ProxyType::Template.filter(&batch(vec![template()])));
+		assert!(!ProxyType::Template.filter(&transfer()));
+		assert!(!ProxyType::Template.filter(&remark()));
+		assert!(!ProxyType::Template.filter(&Call::Utility(pallet_utility::Call::as_derivative {
+			index: 0,
+			call: Box::new(template()),
+		})));
+	}
+
+	#[test]
+	fn proxy_types_are_ordered_by_the_calls_they_allow() {
+		use ProxyType::*;
+		for proxy_type in [Any, NonTransfer, Template] {
+			assert!(Any.is_superset(&proxy_type));
+			assert!(proxy_type.is_superset(&proxy_type));
+		}
+		// `Template` allows `transfer_something`, which `NonTransfer` doesn't.
+		assert!(!NonTransfer.is_superset(&Template));
+		assert!(!NonTransfer.is_superset(&Any));
+		assert!(!Template.is_superset(&NonTransfer));
+		assert!(!Template.is_superset(&Any));
+	}
+
+	#[test]
+	fn proxies_only_dispatch_the_calls_their_type_allows() {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(account(1), 100 * UNIT), (account(2), 100 * UNIT)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		sp_io::TestExternalities::from(t).execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Proxy::add_proxy(
+				Origin::signed(account(1)),
+				account(2),
+				ProxyType::Template,
+				0
+			));
+			let proxy = |call| Proxy::proxy(Origin::signed(account(2)), account(1), None, call);
+
+			// Template calls are dispatched as the proxied account.
+			assert_ok!(proxy(Box::new(template())));
+			assert_eq!(TemplateModule::value_of(&account(1)), Some(42));
+			assert_eq!(TemplateModule::value_of(&account(2)), None);
+
+			// Transfers are filtered, also when hidden in a batch.
+			assert_ok!(proxy(Box::new(transfer())));
+			System::assert_last_event(
+				pallet_proxy::Event::ProxyExecuted(Err(
+					frame_system::Error::<Runtime>::CallFiltered.into(),
+				))
+				.into(),
+			);
+			assert_ok!(proxy(Box::new(batch(vec![template(), transfer()]))));
+			assert_eq!(Balances::free_balance(account(3)), 0);
+		});
+	}
+
+	#[test]
+	fn genesis_vesting_unlocks_linearly() {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(account(1), 100 * UNIT), (account(2), 10 * UNIT)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+		// 90 units unlock over 90 blocks from block 10, 10 are liquid from the start.
+		pallet_vesting::GenesisConfig::<Runtime> { vesting: vec![(account(1), 10, 90, 10 * UNIT)] }
+			.assimilate_storage(&mut t)
+			.unwrap();
+
+		sp_io::TestExternalities::from(t).execute_with(|| {
+			let send =
+				|amount| Balances::transfer(Origin::signed(account(1)), account(2).into(), amount);
+			System::set_block_number(1);
+			assert_eq!(Balances::locks(account(1)).len(), 1);
+			assert_eq!(Balances::usable_balance(account(1)), 10 * UNIT);
+			assert!(send(11 * UNIT).is_err());
+
+			System::set_block_number(55);
+			assert_ok!(Vesting::vest(Origin::signed(account(1))));
+			assert_eq!(Balances::usable_balance(account(1)), 55 * UNIT);
+
+			// Someone else can unlock the vested balance for the account, too.
+			System::set_block_number(100);
+			assert_ok!(Vesting::vest_other(Origin::signed(account(2)), account(1).into()));
+			assert!(Balances::locks(account(1)).is_empty());
+			assert_ok!(send(100 * UNIT));
+		});
+	}
+
+	#[test]
+	fn block_production_governance_and_sudo_cannot_be_paused() {
+		let t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		sp_io::TestExternalities::from(t).execute_with(|| {
+			for pallet in [
+				"System",
+				"Sudo",
+				"Timestamp",
+				"TransactionPause",
+				"Council",
+				"Democracy",
+				"Scheduler",
+				"Preimage",
+			] {
+				assert_noop!(
+					TransactionPause::pause_pallet(Origin::root(), pallet.as_bytes().to_vec()),
+					pallet_transaction_pause::Error::<Runtime>::CannotPause
+				);
+			}
+			assert_ok!(TransactionPause::pause_pallet(Origin::root(), b"TemplateModule".to_vec()));
+			assert!(!<Runtime as frame_system::Config>::BaseCallFilter::contains(&template()));
+		});
+	}
+
+	#[test]
+	fn pool_rejects_paused_calls_and_wrappers_around_them() {
+		use sp_runtime::transaction_validity::UnknownTransaction;
+		use sp_transaction_pool::runtime_api::TaggedTransactionQueue;
+
+		let validate = |call: Call| {
+			<Runtime as TaggedTransactionQueue<Block>>::validate_transaction(
+				TransactionSource::External,
+				UncheckedExtrinsic::new_unsigned(call),
+				Default::default(),
+			)
+		};
+		let paused: TransactionValidity = InvalidTransaction::Call.into();
+		// Balances has no `ValidateUnsigned`, so unpaused unsigned calls are rejected for a
+		// different reason than paused ones.
+		let unpaused: TransactionValidity = UnknownTransaction::NoUnsignedValidator.into();
+		let proxy = |call: Call| {
+			Call::Proxy(pallet_proxy::Call::proxy {
+				real: account(1).into(),
+				force_proxy_type: None,
+				call: Box::new(call),
+			})
+		};
+		let multisig = |call: Call| {
+			Call::Multisig(pallet_multisig::Call::as_multi {
+				threshold: 2,
+				other_signatories: vec![account(2)],
+				maybe_timepoint: None,
+				call: WrapperKeepOpaque::from_encoded(call.encode()),
+				store_call: false,
+				max_weight: 0,
+			})
+		};
+
+		let t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		sp_io::TestExternalities::from(t).execute_with(|| {
+			assert_eq!(validate(transfer()), unpaused);
+			assert_eq!(validate(batch(vec![remark(), transfer()])), unpaused);
+
+			assert_ok!(TransactionPause::pause_call(
+				Origin::root(),
+				b"Balances".to_vec(),
+				b"transfer".to_vec()
+			));
+			assert_eq!(validate(transfer()), paused);
+			assert_eq!(validate(batch(vec![remark(), transfer()])), paused);
+			assert_eq!(validate(proxy(transfer())), paused);
+			assert_eq!(validate(multisig(transfer())), paused);
+			assert_eq!(validate(batch(vec![remark()])), unpaused);
+		});
+	}
+}
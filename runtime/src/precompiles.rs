@@ -0,0 +1,61 @@
+//! The precompiled contracts available to the EVM.
+
+use pallet_evm::{Context, Precompile, PrecompileResult, PrecompileSet};
+use pallet_evm_precompile_modexp::Modexp;
+use pallet_evm_precompile_simple::{ECRecover, Identity, Ripemd160, Sha256};
+use sp_core::H160;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// The precompiles of the Ethereum mainnet up to `modexp`, at the same addresses, so that
+/// contracts written for Ethereum that rely on them work unchanged.
+pub struct EthereumPrecompiles<R>(PhantomData<R>);
+
+impl<R> EthereumPrecompiles<R> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+
+	/// The addresses the precompiles are deployed at.
+	pub fn used_addresses() -> Vec<H160> {
+		(1..=5).map(address).collect()
+	}
+}
+
+impl<R> Default for EthereumPrecompiles<R> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<R: pallet_evm::Config> PrecompileSet for EthereumPrecompiles<R> {
+	fn execute(
+		&self,
+		address: H160,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> Option<PrecompileResult> {
+		match address {
+			a if a == self::address(1) =>
+				Some(ECRecover::execute(input, target_gas, context, is_static)),
+			a if a == self::address(2) =>
+				Some(Sha256::execute(input, target_gas, context, is_static)),
+			a if a == self::address(3) =>
+				Some(Ripemd160::execute(input, target_gas, context, is_static)),
+			a if a == self::address(4) =>
+				Some(Identity::execute(input, target_gas, context, is_static)),
+			a if a == self::address(5) =>
+				Some(Modexp::execute(input, target_gas, context, is_static)),
+			_ => None,
+		}
+	}
+
+	fn is_precompile(&self, address: H160) -> bool {
+		Self::used_addresses().contains(&address)
+	}
+}
+
+fn address(index: u64) -> H160 {
+	H160::from_low_u64_be(index)
+}
@@ -0,0 +1,25 @@
+#!/usr/bin/env bash
+# This script is meant to be run on Unix/Linux based systems
+set -e
+
+# Pallets under ./pallets whose weights come from benchmarks.
+PALLETS=(template block-reward transaction-pause validator-set)
+
+cd $(dirname ${BASH_SOURCE[0]})/..
+
+cargo build --release --features runtime-benchmarks
+
+for pallet in "${PALLETS[@]}"; do
+	echo "*** Regenerating pallet_${pallet//-/_} weights"
+
+	./target/release/node-template benchmark \
+		--chain=dev \
+		--execution=wasm \
+		--wasm-execution=compiled \
+		--pallet=pallet_${pallet//-/_} \
+		--extrinsic='*' \
+		--steps=50 \
+		--repeat=20 \
+		--template=./.maintain/frame-weight-template.hbs \
+		--output=./pallets/${pallet}/src/weights.rs
+done